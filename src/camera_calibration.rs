@@ -1,14 +1,88 @@
-use {Cmcs, Point, Result};
+//! Camera calibrations.
+//!
+//! RiSCAN Pro stores camera calibrations of several kinds under `calibrations/camcalibs`. Opencv
+//! calibrations and the distortion-free pinhole cameras derived from them can project points,
+//! other kinds are kept by name.
+
+use {Cmcs, Point, Result, State};
 use std::path::Path;
 
+/// A camera model, which can project points in the camera's coordinate system onto the image.
+///
+/// Every camera model also has inherent `cmcs_to_ics` and `is_valid_pixel` methods, so callers
+/// only need this trait to handle camera models generically, e.g. as `&dyn CameraModel`.
+///
+/// # Examples
+///
+/// ```
+/// use riscan_pro::{CameraCalibration, CameraModel, Point};
+/// let camera_calibration = CameraCalibration::from_project_path("data/southpole.rsp")
+///     .unwrap()
+///     .pop()
+///     .unwrap();
+/// let camera_model: &dyn CameraModel = &camera_calibration;
+/// let cmcs = Point::cmcs(1.312, -0.641, 3.019);
+/// let (u, v) = camera_model.cmcs_to_ics(&cmcs).unwrap();
+/// assert!(camera_model.is_valid_pixel(u, v));
+/// ```
+pub trait CameraModel {
+    /// Converts a point in the camera's coordinate system to pixel values.
+    ///
+    /// Returns None if the point does not land on the image.
+    fn cmcs_to_ics(&self, point: &Point<Cmcs>) -> Option<(f64, f64)>;
+
+    /// Returns true if this is a valid pixel value.
+    fn is_valid_pixel(&self, u: f64, v: f64) -> bool;
+}
+
 /// A camera calibration.
 ///
-/// Only opencv camera calibrations are supported at this time.
+/// # Examples
+///
+/// ```
+/// use riscan_pro::{CameraCalibration, Point};
+/// let camera_calibration = CameraCalibration::from_project_path("data/southpole.rsp")
+///     .unwrap()
+///     .pop()
+///     .unwrap();
+/// let cmcs = Point::cmcs(1.312, -0.641, 3.019);
+/// let (u, v) = camera_calibration.cmcs_to_ics(&cmcs).unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum CameraCalibration {
+    /// An opencv camera calibration, `camcalib_opencv`.
+    OpenCv(Box<OpenCv>),
+    /// A pinhole camera without any lens distortion, e.g. for undistorted images.
+    Pinhole(Pinhole),
+    /// A camera calibration of a kind that we can't read, e.g. a fisheye calibration.
+    ///
+    /// These can't project points.
+    Other(Other),
+}
+
+/// An opencv camera calibration.
+///
+/// Versions 0, 1, and 2 are read, since they share the same xml layout. Projection always uses
+/// the version 2 math from `project.dtd`, whatever the version.
 #[derive(Clone, Debug, PartialEq, Serialize)]
 #[allow(missing_docs)]
-pub struct CameraCalibration {
+pub struct OpenCv {
+    /// The calibration version, 0, 1, or 2.
+    pub version: u32,
     /// The name of the calibration.
     pub name: String,
+    /// The state of the calibration.
+    pub state: State,
+    /// The camera model, e.g. `Infratec`.
+    pub camera_model: Option<String>,
+    /// The camera's serial number.
+    pub camera_serial_number: Option<String>,
+    /// The lens model.
+    pub lens_model: Option<String>,
+    /// The lens's serial number.
+    pub lens_serial_number: Option<String>,
+    /// Free-form camera settings.
+    pub settings: Option<String>,
     pub cx: f64,
     pub cy: f64,
     pub fx: f64,
@@ -23,10 +97,45 @@ pub struct CameraCalibration {
     pub tan_max_vert: f64,
     pub tan_min_horz: f64,
     pub tan_min_vert: f64,
+    /// The horizontal size of a pixel on the sensor, in meters.
+    pub dx: Option<f64>,
+    /// The vertical size of a pixel on the sensor, in meters.
+    pub dy: Option<f64>,
     pub width: usize,
     pub height: usize,
 }
 
+/// A pinhole camera, without any lens distortion.
+///
+/// RiSCAN Pro doesn't store these, they are made from opencv calibrations by
+/// `CameraCalibration::undistorted`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[allow(missing_docs)]
+pub struct Pinhole {
+    /// The name of the calibration.
+    pub name: String,
+    /// The state of the calibration.
+    pub state: State,
+    pub cx: f64,
+    pub cy: f64,
+    pub fx: f64,
+    pub fy: f64,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// A camera calibration of an unsupported kind.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Other {
+    /// The name of the calibration.
+    pub name: String,
+    /// The state of the calibration.
+    pub state: State,
+    /// The name of the xml element, e.g. `camcalib_fisheye`, which identifies the kind of
+    /// calibration.
+    pub kind: String,
+}
+
 impl CameraCalibration {
     /// Retrieves all camera calibrations from a project.
     ///
@@ -44,9 +153,229 @@ impl CameraCalibration {
         Ok(project.camera_calibrations.values().cloned().collect())
     }
 
+    /// Returns the name of this camera calibration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::CameraCalibration;
+    /// let camera_calibration = CameraCalibration::from_project_path("data/project.RiSCAN")
+    ///     .unwrap()
+    ///     .pop()
+    ///     .unwrap();
+    /// assert_eq!("Infratec_VarioCAM_HD_15mm_11-16-2015_Preston", camera_calibration.name());
+    /// ```
+    pub fn name(&self) -> &str {
+        match *self {
+            CameraCalibration::OpenCv(ref opencv) => &opencv.name,
+            CameraCalibration::Pinhole(ref pinhole) => &pinhole.name,
+            CameraCalibration::Other(ref other) => &other.name,
+        }
+    }
+
+    /// Returns this camera calibration as an opencv calibration, if it is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::CameraCalibration;
+    /// let camera_calibration = CameraCalibration::from_project_path("data/project.RiSCAN")
+    ///     .unwrap()
+    ///     .pop()
+    ///     .unwrap();
+    /// let opencv = camera_calibration.as_opencv().unwrap();
+    /// ```
+    pub fn as_opencv(&self) -> Option<&OpenCv> {
+        match *self {
+            CameraCalibration::OpenCv(ref opencv) => Some(opencv),
+            _ => None,
+        }
+    }
+
+    /// Returns this camera calibration as a pinhole camera, if it is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::CameraCalibration;
+    /// let camera_calibration = CameraCalibration::from_project_path("data/project.RiSCAN")
+    ///     .unwrap()
+    ///     .pop()
+    ///     .unwrap();
+    /// assert_eq!(None, camera_calibration.as_pinhole());
+    /// assert!(camera_calibration.undistorted().as_pinhole().is_some());
+    /// ```
+    pub fn as_pinhole(&self) -> Option<&Pinhole> {
+        match *self {
+            CameraCalibration::Pinhole(ref pinhole) => Some(pinhole),
+            _ => None,
+        }
+    }
+
+    /// Returns this camera calibration without any lens distortion.
+    ///
+    /// This is the camera model for images that have already been undistorted. Opencv calibrations
+    /// become pinhole cameras, other kinds are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::CameraCalibration;
+    /// let camera_calibration = CameraCalibration::from_project_path("data/project.RiSCAN")
+    ///     .unwrap()
+    ///     .pop()
+    ///     .unwrap()
+    ///     .undistorted();
+    /// let pinhole = camera_calibration.as_pinhole().unwrap();
+    /// ```
+    pub fn undistorted(&self) -> CameraCalibration {
+        match *self {
+            CameraCalibration::OpenCv(ref opencv) => CameraCalibration::Pinhole(opencv.pinhole()),
+            _ => self.clone(),
+        }
+    }
+
     /// Converts a point in the camera's coordinate system to pixel values.
     ///
     /// The pixel values are floats, in case someone later wants to do more than a direct lookup.
+    /// Returns None if the point does not land on the image, or if this calibration can't project
+    /// points, see `OpenCv::cmcs_to_ics`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{CameraCalibration, Point};
+    /// let camera_calibration = CameraCalibration::from_project_path("data/southpole.rsp")
+    ///     .unwrap()
+    ///     .pop()
+    ///     .unwrap();
+    /// let cmcs = Point::cmcs(1.312, -0.641, 3.019);
+    /// let (u, v) = camera_calibration.cmcs_to_ics(&cmcs).unwrap();
+    /// ```
+    pub fn cmcs_to_ics(&self, point: &Point<Cmcs>) -> Option<(f64, f64)> {
+        match *self {
+            CameraCalibration::OpenCv(ref opencv) => opencv.cmcs_to_ics(point),
+            CameraCalibration::Pinhole(ref pinhole) => pinhole.cmcs_to_ics(point),
+            CameraCalibration::Other(_) => None,
+        }
+    }
+
+    /// Returns true if this is a valid pixel value.
+    ///
+    /// Calibrations that can't project points have no valid pixels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::CameraCalibration;
+    /// let camera_calibration = CameraCalibration::from_project_path("data/project.RiSCAN")
+    ///     .unwrap()
+    ///     .pop()
+    ///     .unwrap();
+    /// // The camera calibration is 1024x768
+    /// assert!(camera_calibration.is_valid_pixel(0., 0.));
+    /// assert!(!camera_calibration.is_valid_pixel(1024., 0.));
+    /// assert!(!camera_calibration.is_valid_pixel(0., 768.));
+    /// ```
+    pub fn is_valid_pixel<T: Into<f64>>(&self, u: T, v: T) -> bool {
+        match *self {
+            CameraCalibration::OpenCv(ref opencv) => opencv.is_valid_pixel(u, v),
+            CameraCalibration::Pinhole(ref pinhole) => pinhole.is_valid_pixel(u, v),
+            CameraCalibration::Other(_) => false,
+        }
+    }
+}
+
+impl CameraModel for CameraCalibration {
+    fn cmcs_to_ics(&self, point: &Point<Cmcs>) -> Option<(f64, f64)> {
+        CameraCalibration::cmcs_to_ics(self, point)
+    }
+
+    fn is_valid_pixel(&self, u: f64, v: f64) -> bool {
+        CameraCalibration::is_valid_pixel(self, u, v)
+    }
+}
+
+impl OpenCv {
+    /// Returns a copy of this camera calibration with all distortion parameters set to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::CameraCalibration;
+    /// let camera_calibration = CameraCalibration::from_project_path("data/project.RiSCAN")
+    ///     .unwrap()
+    ///     .pop()
+    ///     .unwrap();
+    /// let opencv = camera_calibration.as_opencv().unwrap().undistorted();
+    /// assert_eq!(0., opencv.k1);
+    /// ```
+    pub fn undistorted(&self) -> OpenCv {
+        OpenCv {
+            k1: 0.,
+            k2: 0.,
+            k3: 0.,
+            k4: 0.,
+            p1: 0.,
+            p2: 0.,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the pinhole camera with this calibration's intrinsics, i.e. without distortion.
+    ///
+    /// Unlike an opencv calibration with zeroed distortion parameters, the pinhole camera doesn't
+    /// check the angle extents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::CameraCalibration;
+    /// let camera_calibration = CameraCalibration::from_project_path("data/project.RiSCAN")
+    ///     .unwrap()
+    ///     .pop()
+    ///     .unwrap();
+    /// let opencv = camera_calibration.as_opencv().unwrap();
+    /// let pinhole = opencv.pinhole();
+    /// assert_eq!(opencv.fx, pinhole.fx);
+    /// ```
+    pub fn pinhole(&self) -> Pinhole {
+        Pinhole {
+            name: self.name.clone(),
+            state: self.state.clone(),
+            cx: self.cx,
+            cy: self.cy,
+            fx: self.fx,
+            fy: self.fy,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Returns the horizontal and vertical focal lengths in millimeters.
+    ///
+    /// The focal lengths are calculated from the focal lengths in pixels and the pixel size, so
+    /// this returns None if the calibration doesn't record a pixel size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::CameraCalibration;
+    /// let camera_calibration = CameraCalibration::from_project_path("data/project.RiSCAN")
+    ///     .unwrap()
+    ///     .pop()
+    ///     .unwrap();
+    /// let (fx, fy) = camera_calibration.as_opencv().unwrap().focal_length_mm().unwrap();
+    /// assert_eq!(15., fx.round()); // A 15mm lens
+    /// ```
+    pub fn focal_length_mm(&self) -> Option<(f64, f64)> {
+        match (self.dx, self.dy) {
+            (Some(dx), Some(dy)) => Some((self.fx * dx * 1000., self.fy * dy * 1000.)),
+            _ => None,
+        }
+    }
+
+    /// Converts a point in the camera's coordinate system to pixel values.
     ///
     /// Returns None if:
     ///
@@ -54,7 +383,8 @@ impl CameraCalibration {
     /// - The point is ouside the angle extents, as defined by `tan_{min|max}_{vert|horz}`.
     /// - The calculated pixel values are outside of the width/height of the image.
     ///
-    /// These maths are taken from the `project.dtd` file in every RiSCAN Pro project.
+    /// These maths are taken from the `project.dtd` file in every RiSCAN Pro project, and are the
+    /// version 2 maths regardless of `version`.
     ///
     /// # Examples
     ///
@@ -64,8 +394,9 @@ impl CameraCalibration {
     ///     .unwrap()
     ///     .pop()
     ///     .unwrap();
+    /// let opencv = camera_calibration.as_opencv().unwrap();
     /// let cmcs = Point::cmcs(1.312, -0.641, 3.019);
-    /// let (u, v) = camera_calibration.cmcs_to_ics(&cmcs).unwrap();
+    /// let (u, v) = opencv.cmcs_to_ics(&cmcs).unwrap();
     /// ```
     pub fn cmcs_to_ics(&self, point: &Point<Cmcs>) -> Option<(f64, f64)> {
         use nalgebra::Matrix3;
@@ -114,28 +445,106 @@ impl CameraCalibration {
     ///     .unwrap()
     ///     .pop()
     ///     .unwrap();
+    /// let opencv = camera_calibration.as_opencv().unwrap();
     /// // The camera calibration is 1024x768
-    /// assert!(camera_calibration.is_valid_pixel(0., 0.));
-    /// assert!(!camera_calibration.is_valid_pixel(1024., 0.));
-    /// assert!(!camera_calibration.is_valid_pixel(0., 768.));
+    /// assert!(opencv.is_valid_pixel(0., 0.));
+    /// assert!(!opencv.is_valid_pixel(1024., 0.));
+    /// assert!(!opencv.is_valid_pixel(0., 768.));
     /// ```
     pub fn is_valid_pixel<T: Into<f64>>(&self, u: T, v: T) -> bool {
-        let u = u.into();
-        let v = v.into();
-        u >= 0. && v >= 0. && u < self.width as f64 && v < self.height as f64
+        is_valid_pixel(u.into(), v.into(), self.width, self.height)
+    }
+}
+
+impl CameraModel for OpenCv {
+    fn cmcs_to_ics(&self, point: &Point<Cmcs>) -> Option<(f64, f64)> {
+        OpenCv::cmcs_to_ics(self, point)
+    }
+
+    fn is_valid_pixel(&self, u: f64, v: f64) -> bool {
+        OpenCv::is_valid_pixel(self, u, v)
     }
 }
 
+impl Pinhole {
+    /// Converts a point in the camera's coordinate system to pixel values.
+    ///
+    /// Returns None if the point is behind the camera or outside of the width/height of the image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{CameraCalibration, Point};
+    /// let camera_calibration = CameraCalibration::from_project_path("data/southpole.rsp")
+    ///     .unwrap()
+    ///     .pop()
+    ///     .unwrap();
+    /// let pinhole = camera_calibration.as_opencv().unwrap().pinhole();
+    /// let (u, v) = pinhole.cmcs_to_ics(&Point::cmcs(0., 0., 1.)).unwrap();
+    /// assert_eq!(pinhole.cx, u);
+    /// assert_eq!(pinhole.cy, v);
+    /// ```
+    pub fn cmcs_to_ics(&self, point: &Point<Cmcs>) -> Option<(f64, f64)> {
+        if point.is_behind_camera() {
+            return None;
+        }
+        let u = self.fx * point.x / point.z + self.cx;
+        let v = self.fy * point.y / point.z + self.cy;
+        if self.is_valid_pixel(u, v) {
+            Some((u, v))
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if this is a valid pixel value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::CameraCalibration;
+    /// let camera_calibration = CameraCalibration::from_project_path("data/project.RiSCAN")
+    ///     .unwrap()
+    ///     .pop()
+    ///     .unwrap();
+    /// let pinhole = camera_calibration.as_opencv().unwrap().pinhole();
+    /// // The camera calibration is 1024x768
+    /// assert!(pinhole.is_valid_pixel(0., 0.));
+    /// assert!(!pinhole.is_valid_pixel(1024., 0.));
+    /// ```
+    pub fn is_valid_pixel<T: Into<f64>>(&self, u: T, v: T) -> bool {
+        is_valid_pixel(u.into(), v.into(), self.width, self.height)
+    }
+}
+
+impl CameraModel for Pinhole {
+    fn cmcs_to_ics(&self, point: &Point<Cmcs>) -> Option<(f64, f64)> {
+        Pinhole::cmcs_to_ics(self, point)
+    }
+
+    fn is_valid_pixel(&self, u: f64, v: f64) -> bool {
+        Pinhole::is_valid_pixel(self, u, v)
+    }
+}
+
+fn is_valid_pixel(u: f64, v: f64, width: usize, height: usize) -> bool {
+    u >= 0. && v >= 0. && u < width as f64 && v < height as f64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn cmcs_to_ics() {
-        let camera_calibration = CameraCalibration::from_project_path("data/southpole.rsp")
+    fn southpole() -> CameraCalibration {
+        CameraCalibration::from_project_path("data/southpole.rsp")
             .unwrap()
             .pop()
-            .unwrap();
+            .unwrap()
+    }
+
+    #[test]
+    fn cmcs_to_ics() {
+        let camera_calibration = southpole();
         let cmcs = Point::cmcs(1.312, -0.641, 3.019);
         let (u, v) = camera_calibration.cmcs_to_ics(&cmcs).unwrap();
         assert_relative_eq!(882.668, u, epsilon = 1e-3);
@@ -150,11 +559,27 @@ mod tests {
     }
 
     #[test]
-    fn is_valid_pixel() {
-        let camera_calibration = CameraCalibration::from_project_path("data/southpole.rsp")
+    fn metadata() {
+        let camera_calibration = CameraCalibration::from_project_path("data/project.RiSCAN")
             .unwrap()
             .pop()
             .unwrap();
+        let camera_calibration = camera_calibration.as_opencv().unwrap();
+        assert_eq!(Some("Infratec"), camera_calibration.camera_model.as_deref());
+        assert_eq!(Some(""), camera_calibration.camera_serial_number.as_deref());
+        assert_eq!(Some(""), camera_calibration.lens_model.as_deref());
+        assert_eq!(Some(""), camera_calibration.lens_serial_number.as_deref());
+        assert_eq!(Some(""), camera_calibration.settings.as_deref());
+        assert_eq!(Some(1.7e-5), camera_calibration.dx);
+        assert_eq!(Some(1.7e-5), camera_calibration.dy);
+        let (fx, fy) = camera_calibration.focal_length_mm().unwrap();
+        assert_relative_eq!(15.020, fx, epsilon = 1e-3);
+        assert_relative_eq!(15.032, fy, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn is_valid_pixel() {
+        let camera_calibration = southpole();
         assert!(camera_calibration.is_valid_pixel(0, 0));
         assert!(!camera_calibration.is_valid_pixel(-1, 0));
         assert!(!camera_calibration.is_valid_pixel(0, -1));
@@ -163,4 +588,41 @@ mod tests {
         assert!(camera_calibration.is_valid_pixel(1023.9, 0.));
         assert!(camera_calibration.is_valid_pixel(0., 767.9));
     }
+
+    #[test]
+    fn pinhole_matches_undistorted_opencv() {
+        let opencv = southpole().as_opencv().unwrap().undistorted();
+        let pinhole = southpole().undistorted();
+        let cmcs = Point::cmcs(1.312, -0.641, 3.019);
+        let (u, v) = opencv.cmcs_to_ics(&cmcs).unwrap();
+        let (pinhole_u, pinhole_v) = pinhole.cmcs_to_ics(&cmcs).unwrap();
+        assert_relative_eq!(u, pinhole_u);
+        assert_relative_eq!(v, pinhole_v);
+        assert_eq!(southpole().name(), pinhole.name());
+        assert_eq!(None, pinhole.as_opencv());
+    }
+
+    #[test]
+    fn camera_model() {
+        let camera_calibration = southpole();
+        let pinhole = camera_calibration.undistorted();
+        let camera_models: Vec<&dyn CameraModel> = vec![&camera_calibration, &pinhole];
+        for camera_model in camera_models {
+            assert!(camera_model.cmcs_to_ics(&Point::cmcs(0., 0., 1.)).is_some());
+            assert!(!camera_model.is_valid_pixel(-1., 0.));
+        }
+    }
+
+    #[test]
+    fn other() {
+        let other = CameraCalibration::Other(Other {
+            name: "fisheye".to_string(),
+            state: State::Default,
+            kind: "camcalib_fisheye".to_string(),
+        });
+        assert_eq!("fisheye", other.name());
+        assert_eq!(None, other.as_opencv());
+        assert_eq!(None, other.cmcs_to_ics(&Point::cmcs(0., 0., 1.)));
+        assert!(!other.is_valid_pixel(0, 0));
+    }
 }
@@ -0,0 +1,60 @@
+use {Error, Node, NodeRef, Point, Project, ReflectorCalibration, Result};
+use point::CoordinateReferenceSystem;
+
+/// A project-level tie point, in either the project's or the global coordinate system.
+///
+/// These are the control points used to register scan positions to each other and to georeference
+/// the project.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ControlPoint<C: CoordinateReferenceSystem> {
+    /// The name of the control point.
+    pub name: String,
+    /// The position of the control point.
+    pub position: Point<C>,
+    /// The height of the target above the control point.
+    pub height: f64,
+    /// The control point is used for registration.
+    pub is_active: bool,
+    /// The control point's position is fixed during registration.
+    ///
+    /// Only project coordinate system control points can be fixed.
+    pub is_fixed: bool,
+    /// The weight of the control point during registration, if one is set.
+    pub weight: Option<f64>,
+    /// The reference to the control point's reflector calibration, if there is one.
+    pub reflector_calibration_ref: Option<NodeRef>,
+    /// The control point that this control point is linked to, e.g. a GLCS control point.
+    pub linked: Option<NodeRef>,
+    /// The tie points that are linked to this control point.
+    pub referrers: Vec<NodeRef>,
+}
+
+impl<C: CoordinateReferenceSystem> ControlPoint<C> {
+    /// Finds and returns this control point's reflector calibration, if it has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/southpole.rsp").unwrap();
+    /// let control_point = project.prcs_control_points.get("TP1-average").unwrap();
+    /// let reflector_calibration = control_point.reflector_calibration(&project)
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!("RIEGL Cylinder 10 cm", reflector_calibration.name);
+    /// ```
+    pub fn reflector_calibration<'a>(
+        &self,
+        project: &'a Project,
+    ) -> Result<Option<&'a ReflectorCalibration>> {
+        match self.reflector_calibration_ref {
+            Some(ref noderef) => match project.resolve(noderef) {
+                Ok(Node::ReflectorCalibration(reflector_calibration)) => {
+                    Ok(Some(reflector_calibration))
+                }
+                _ => Err(Error::MissingReflectorCalibration(noderef.path.clone())),
+            },
+            None => Ok(None),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Improvements to `xmltree::Element`.
 
-use {Error, Result};
+use {Error, NodeRef, Result};
 use std::str::FromStr;
 use xmltree::Element;
 
@@ -48,6 +48,31 @@ pub trait Extension {
     /// ```
     fn children(&self, path: &str) -> Result<&Vec<Element>>;
 
+    /// Returns a vector of children with the given name, skipping any other children.
+    ///
+    /// Unlike `children`, the parent element may contain children with other names, e.g. the
+    /// `active` flag at the top of every tie point list.
+    ///
+    /// # Examples
+    ///
+    /// `Extension` is implemented for `xmltree::Element`:
+    ///
+    /// ```
+    /// extern crate xmltree;
+    /// # extern crate riscan_pro;
+    /// # fn main () {
+    /// use xmltree::Element;
+    /// use riscan_pro::element::Extension;
+    /// use std::fs::File;
+    ///
+    /// let file = File::open("data/project.RiSCAN/project.rsp").unwrap();
+    /// let element = Element::parse(file).unwrap();
+    /// let scan_position = element.child("scanpositions/scanposition").unwrap();
+    /// let tie_points = scan_position.children_named("tpl_socs/tp_socs").unwrap();
+    /// # }
+    /// ```
+    fn children_named(&self, path: &str) -> Result<Vec<&Element>>;
+
     /// Returns this element's inner text as a string, or returns an error if there is no text.
     ///
     /// # Examples
@@ -69,9 +94,10 @@ pub trait Extension {
     /// ```
     fn as_str(&self) -> Result<&str>;
 
-    /// Returns this element's nodref attribute as a string.
+    /// Returns this element's noderef attribute.
     ///
-    /// The noderef is trimmed to only use the last element.
+    /// The noderef keeps its full path, use `NodeRef::name` to get the name of the referenced
+    /// object.
     ///
     /// # Examples
     ///
@@ -92,10 +118,10 @@ pub trait Extension {
     ///     .unwrap()
     ///     .noderef()
     ///     .unwrap();
-    /// assert_eq!("Logo", logo);
+    /// assert_eq!("Logo", logo.name());
     /// # }
     /// ```
-    fn noderef(&self) -> Result<&str>;
+    fn noderef(&self) -> Result<NodeRef>;
 
     /// Parses this element's inner text, or returns an error if there is no text or if the parse
     /// fails.
@@ -144,18 +170,30 @@ impl Extension for Element {
         }
     }
 
+    fn children_named(&self, path: &str) -> Result<Vec<&Element>> {
+        let mut iter = path.split('/').rev();
+        let last = iter.next().ok_or_else(|| {
+            Error::MissingChild(self.name.clone(), String::new())
+        })?;
+        let element = burrow(self, iter.rev())?;
+        Ok(
+            element
+                .children
+                .iter()
+                .filter(|child| child.name == last)
+                .collect(),
+        )
+    }
+
     fn as_str(&self) -> Result<&str> {
         self.text.as_ref().map(|s| s.as_str()).ok_or_else(|| {
             Error::NoElementText(self.clone())
         })
     }
 
-    fn noderef(&self) -> Result<&str> {
-        if let Some(noderef) = self.attributes.get("noderef").and_then(
-            |s| s.split('/').last(),
-        )
-        {
-            Ok(noderef)
+    fn noderef(&self) -> Result<NodeRef> {
+        if let Some(noderef) = self.attributes.get("noderef") {
+            Ok(NodeRef::new(noderef.as_str()))
         } else {
             Err(Error::MissingNoderef(self.clone()))
         }
@@ -210,6 +248,22 @@ mod tests {
         assert!(project.children("not-an-element").is_err());
     }
 
+    #[test]
+    fn children_named() {
+        let project = project();
+        let scan_position = project.child("scanpositions/scanposition").unwrap();
+        assert!(scan_position.children("tpl_socs/tp_socs").is_err());
+        let tie_points = scan_position.children_named("tpl_socs/tp_socs").unwrap();
+        assert!(tie_points.iter().all(|tie_point| tie_point.name == "tp_socs"));
+        assert!(
+            project
+                .children_named("tpl_prcs/tp_prcs")
+                .unwrap()
+                .is_empty()
+        );
+        assert!(project.children_named("not-an-element/child").is_err());
+    }
+
     #[test]
     fn as_str() {
         let project = project();
@@ -230,14 +284,18 @@ mod tests {
                 .unwrap()
                 .noderef()
                 .unwrap()
+                .name()
         );
+        let noderef = project
+            .child("geometry_objects/images/image/camcalib_ref")
+            .unwrap()
+            .noderef()
+            .unwrap();
+        assert_eq!("Infratec_VarioCAM_HD_15mm_11-16-2015_Preston", noderef.name());
         assert_eq!(
-            "Infratec_VarioCAM_HD_15mm_11-16-2015_Preston",
-            project
-                .child("geometry_objects/images/image/camcalib_ref")
-                .unwrap()
-                .noderef()
-                .unwrap()
+            "/2.2.1_Test_InfraTec_VZ1000_Calibration/CALIBRATIONS/CAMERA/Infratec_VarioCAM_HD_15mm_11-16-2015_Preston",
+            noderef.path
         );
+        assert!(project.noderef().is_err());
     }
 }
@@ -0,0 +1,78 @@
+//! Geometry objects digitized in RiSCAN Pro.
+//!
+//! Only polylines are typed for now, the other kinds are `ObjectRecord`s on the `Project`.
+
+use {Glcs, Point, Prcs};
+use nalgebra::Projective3;
+use point::CoordinateReferenceSystem;
+
+/// A polyline digitized in RiSCAN Pro.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct Polyline<C: CoordinateReferenceSystem> {
+    /// The name of the polyline.
+    pub name: String,
+    /// The vertices of the polyline, in order.
+    pub points: Vec<Point<C>>,
+}
+
+impl Polyline<Prcs> {
+    /// Converts this polyline to the global coordinate system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/southpole.rsp").unwrap();
+    /// let polyline = project.polylines["Polyline001"].to_glcs(project.pop);
+    /// ```
+    pub fn to_glcs(&self, pop: Projective3<f64>) -> Polyline<Glcs> {
+        Polyline {
+            name: self.name.clone(),
+            points: self.points.iter().map(|point| point.to_glcs(pop)).collect(),
+        }
+    }
+}
+
+impl<C: CoordinateReferenceSystem> Polyline<C> {
+    /// Returns the length of this polyline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/southpole.rsp").unwrap();
+    /// let length = project.polylines["Polyline001"].length();
+    /// ```
+    pub fn length(&self) -> f64 {
+        use nalgebra;
+        self.points
+            .windows(2)
+            .map(|points| nalgebra::distance(&*points[0], &*points[1]))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Project;
+
+    #[test]
+    fn polyline() {
+        use std::ops::Deref;
+
+        let project = Project::from_path("data/southpole.rsp").unwrap();
+        assert_eq!(1, project.polylines.len());
+        let polyline = &project.polylines["Polyline001"];
+        assert_eq!(
+            Point::prcs(771.04296875, 54.166667938232422, 2838.5395507812499),
+            polyline.points[0]
+        );
+        assert_eq!(polyline.points[0], *polyline.points.last().unwrap());
+        let glcs = polyline.to_glcs(project.pop);
+        assert_eq!(polyline.points.len(), glcs.points.len());
+        let expected = polyline.points[1].to_glcs(project.pop);
+        assert_eq!(expected.deref(), glcs.points[1].deref());
+        assert_relative_eq!(polyline.length(), glcs.length(), epsilon = 1e-6);
+    }
+}
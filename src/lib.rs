@@ -42,31 +42,49 @@
 #![deny(missing_docs, missing_debug_implementations, missing_copy_implementations, trivial_casts,
         trivial_numeric_casts, unsafe_code, unstable_features, unused_import_braces,
         unused_qualifications)]
-#![recursion_limit="128"]
+#![recursion_limit="256"]
 
 #[cfg(test)]
 #[macro_use]
 extern crate approx;
+extern crate chrono;
 extern crate nalgebra;
 #[macro_use]
 extern crate quick_error;
+extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 extern crate xmltree;
 
-mod camera_calibration;
+pub mod camera_calibration;
+mod control_point;
 pub mod element;
+mod geometry;
 mod mount_calibration;
+mod node_ref;
+mod object_record;
 mod point;
 mod project;
+mod reflector_calibration;
+mod registration;
 pub mod scan_position;
+mod state;
 pub mod utils;
+mod writer;
 
-pub use camera_calibration::CameraCalibration;
+pub use camera_calibration::{CameraCalibration, CameraModel};
+pub use control_point::ControlPoint;
+pub use geometry::Polyline;
 pub use mount_calibration::MountCalibration;
-pub use point::{Cmcs, Glcs, Point, Prcs, Socs};
-pub use project::Project;
+pub use node_ref::{Node, NodeRef};
+pub use object_record::ObjectRecord;
+pub use point::{BoundingBox, Cmcs, Glcs, Point, Prcs, Socs};
+pub use project::{Project, ProjectImage, ProjectInfo};
+pub use reflector_calibration::{ReflectorCalibration, ReflectorShape};
+pub use registration::{RegistrationEdge, RegistrationGraph, RegistrationMethod};
 pub use scan_position::ScanPosition;
+pub use state::State;
+pub use writer::ProjectWriter;
 
 quick_error! {
 /// Our custom error enum.
@@ -77,6 +95,11 @@ quick_error! {
             description("invalid camera calibration version")
             display("This camera calibration version is not supported: {}", version)
         }
+        /// A node reference does not point to anything in the project.
+        DanglingNodeRef(noderef: String) {
+            description("the node reference does not point to anything")
+            display("The node reference does not point to anything: {}", noderef)
+        }
         /// Given a path and a project, could not find an image.
         ImageFromPath(path: std::path::PathBuf) {
             description("could not create image from project and path")
@@ -89,31 +112,71 @@ quick_error! {
             from()
             cause(err)
         }
-        /// There is no camera calibration with the given name.
-        MissingCameraCalibration(name: String) {
+        /// The project xml could not be scanned, e.g. because a tag is not closed.
+        MalformedXml(position: usize) {
+            description("the xml is malformed")
+            display("The xml is malformed at byte {}", position)
+        }
+        /// There is no camera calibration at the given node reference.
+        MissingCameraCalibration(noderef: String) {
             description("the camera calibration does not exist")
-            display("The camera calibration does not exist: {}", name)
+            display("The camera calibration does not exist: {}", noderef)
         }
         /// A requested xml element child does not exist.
         MissingChild(parent: String, child: String) {
             description("the child element does not exist")
             display("The element {} is not a child of {}", parent, child)
         }
-        /// There is no mount calibration with the given name.
-        MissingMountCalibration(name: String) {
+        /// There is no element at the given path.
+        MissingElement(path: String) {
+            description("the element does not exist")
+            display("The element does not exist: {}", path)
+        }
+        /// There is no image at the given node reference.
+        MissingImage(noderef: String) {
+            description("the image does not exist")
+            display("The image does not exist: {}", noderef)
+        }
+        /// There is no mount calibration at the given node reference.
+        MissingMountCalibration(noderef: String) {
             description("the mount calibration does not exist")
-            display("The mount calibration does not exist: {}", name)
+            display("The mount calibration does not exist: {}", noderef)
         }
         /// There is no noderef attribute on an element.
         MissingNoderef(element: xmltree::Element) {
             description("the element does not have a noderef attribute")
             display("The element named {} does not have a noderef attribute", element.name)
         }
+        /// There is no reflector calibration at the given node reference.
+        MissingReflectorCalibration(noderef: String) {
+            description("the reflector calibration does not exist")
+            display("The reflector calibration does not exist: {}", noderef)
+        }
+        /// There is no scan configuration at the given node reference.
+        MissingScanConfiguration(noderef: String) {
+            description("the scan configuration does not exist")
+            display("The scan configuration does not exist: {}", noderef)
+        }
+        /// There is no tie point with the given name.
+        MissingTiePoint(name: String) {
+            description("the tie point does not exist")
+            display("The tie point does not exist: {}", name)
+        }
+        /// There is no tiepoint scan at the given node reference.
+        MissingTiepointScan(noderef: String) {
+            description("the tiepoint scan does not exist")
+            display("The tiepoint scan does not exist: {}", noderef)
+        }
         /// The element does not have any text, when it was required.
         NoElementText(element: xmltree::Element) {
             description("the element does not have text")
             display("The element named {} does not have text", element.name)
         }
+        /// Unable to parse text as a duration.
+        ParseDuration(text: String) {
+            description("cannot parse text as a duration")
+            display("Cannot parse text as a duration: {}", text)
+        }
         /// Wrapper around `std::num::ParseFloatError`.
         ParseFloat(err: std::num::ParseFloatError) {
             description(err.description())
@@ -133,6 +196,16 @@ quick_error! {
             description("cannot parse text as Projective3")
             display("Cannot parse text as Projective3: {}", text)
         }
+        /// Unable to parse text as a RiSCAN Pro timestamp.
+        ParseTimestamp(text: String) {
+            description("cannot parse text as a timestamp")
+            display("Cannot parse text as a timestamp: {}", text)
+        }
+        /// Unable to parse text as a three-dimensional vector.
+        ParseVector3(text: String) {
+            description("cannot parse text as Vector3")
+            display("Cannot parse text as Vector3: {}", text)
+        }
         /// The path is not a valid project path.
         ///
         /// Valid project paths either end in .rsp or .RiSCAN.
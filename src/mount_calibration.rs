@@ -1,3 +1,4 @@
+use State;
 use nalgebra::Projective3;
 use std::ops::Deref;
 
@@ -8,6 +9,10 @@ pub struct MountCalibration {
     pub matrix: Projective3<f64>,
     /// The name of the calibration matrix.
     pub name: String,
+    /// The state of the calibration.
+    pub state: State,
+    /// The calibration has been adjusted.
+    pub is_adjusted: bool,
 }
 
 impl Deref for MountCalibration {
@@ -0,0 +1,115 @@
+use {CameraCalibration, ControlPoint, Glcs, MountCalibration, ObjectRecord, Polyline, Prcs,
+     ProjectImage, ReflectorCalibration};
+use scan_position::{DataFile, Image, ImageTiePoint, Scan, ScanPosition, TiePoint};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A reference from one RiSCAN Pro object to another, e.g. from an image to its camera
+/// calibration.
+///
+/// Node references are slash-separated paths from the project root, e.g.
+/// `/Project/CALIBRATIONS/CAMERA/Camera`. Use `Project::resolve` to find the referenced object.
+///
+/// # Examples
+///
+/// ```
+/// use riscan_pro::NodeRef;
+/// let noderef = NodeRef::new("/Project/CALIBRATIONS/CAMERA/Camera");
+/// assert_eq!(vec!["Project", "CALIBRATIONS", "CAMERA", "Camera"], noderef.segments());
+/// assert_eq!("Camera", noderef.name());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct NodeRef {
+    /// The full path of the reference.
+    pub path: String,
+}
+
+/// An object in a project, as referenced by a `NodeRef`.
+///
+/// Objects that belong to a scan position carry their scan position along with them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Node<'a> {
+    /// A camera calibration, `CALIBRATIONS/CAMERA/<name>`.
+    CameraCalibration(&'a CameraCalibration),
+    /// A mount calibration, `CALIBRATIONS/MOUNTING/<name>`.
+    MountCalibration(&'a MountCalibration),
+    /// A reflector calibration, `CALIBRATIONS/REFLECTOR/<name>`.
+    ReflectorCalibration(&'a ReflectorCalibration),
+    /// A project image, `OBJECTS/IMAGES/<name>`.
+    ProjectImage(&'a ProjectImage),
+    /// A polyline, `OBJECTS/POLYLINES/<name>`.
+    Polyline(&'a Polyline<Prcs>),
+    /// A scan configuration, `COLLECTIONS/CONFIGS/<name>`.
+    ScanConfiguration(&'a ObjectRecord),
+    /// The project's control point list, `TPL (PRCS)`.
+    PrcsControlPoints(&'a BTreeMap<String, ControlPoint<Prcs>>),
+    /// A control point in the project's control point list, `TPL (PRCS)/<name>`.
+    PrcsControlPoint(&'a ControlPoint<Prcs>),
+    /// The global control point list, `TPL (GLCS)`.
+    GlcsControlPoints(&'a BTreeMap<String, ControlPoint<Glcs>>),
+    /// A control point in the global control point list, `TPL (GLCS)/<name>`.
+    GlcsControlPoint(&'a ControlPoint<Glcs>),
+    /// A scan position, `SCANS/<name>`.
+    ScanPosition(&'a ScanPosition),
+    /// A single scan, `SCANS/<scan position>/SINGLESCANS/<name>`.
+    Scan(&'a ScanPosition, &'a Scan),
+    /// A tiepoint scan, `SCANS/<scan position>/TIEPOINTSCANS/<name>`.
+    TiepointScan(&'a ScanPosition, &'a Scan),
+    /// An RDB2 point cloud, `SCANS/<scan position>/POINTCLOUDS/<name>`.
+    PointCloud(&'a ScanPosition, &'a DataFile),
+    /// A scan position image, `SCANS/<scan position>/SCANPOSIMAGES/<name>`.
+    Image(&'a ScanPosition, &'a Image),
+    /// An image tie point, `SCANS/<scan position>/SCANPOSIMAGES/<image>/TPL (IMAGE)/<name>`.
+    ImageTiePoint(&'a ScanPosition, &'a Image, &'a ImageTiePoint),
+    /// A tie point in a scan position's tie point list, `SCANS/<scan position>/TPL (SOCS)/<name>`.
+    TiePoint(&'a ScanPosition, &'a TiePoint),
+}
+
+impl NodeRef {
+    /// Creates a new node reference from its path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::NodeRef;
+    /// let noderef = NodeRef::new("/Project/SCANS/SP01");
+    /// ```
+    pub fn new<S: Into<String>>(path: S) -> NodeRef {
+        NodeRef { path: path.into() }
+    }
+
+    /// Returns the segments of this reference's path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::NodeRef;
+    /// let noderef = NodeRef::new("/Project/SCANS/SP01");
+    /// assert_eq!(vec!["Project", "SCANS", "SP01"], noderef.segments());
+    /// ```
+    pub fn segments(&self) -> Vec<&str> {
+        self.path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect()
+    }
+
+    /// Returns the name of the referenced object, i.e. the last segment of the path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::NodeRef;
+    /// let noderef = NodeRef::new("/Project/SCANS/SP01");
+    /// assert_eq!("SP01", noderef.name());
+    /// ```
+    pub fn name(&self) -> &str {
+        self.segments().pop().unwrap_or("")
+    }
+}
+
+impl fmt::Display for NodeRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.path)
+    }
+}
@@ -0,0 +1,17 @@
+use State;
+
+/// A RiSCAN Pro object of which only the name, kind, and state are read.
+///
+/// Every RiSCAN Pro object carries its name and kind as attributes, and its state, e.g. deleted,
+/// in its `states` attribute. None of our sample projects have any tie objects, geometry objects
+/// other than polylines, pose estimations, tilt mount calibrations, or scan configurations, so
+/// their layout is unknown and these objects are kept as records of just those attributes.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ObjectRecord {
+    /// The name of the object.
+    pub name: String,
+    /// The RiSCAN Pro kind of the object, from its `kind` attribute, e.g. `tiltmount`.
+    pub kind: String,
+    /// The state of the object.
+    pub state: State,
+}
@@ -1,5 +1,6 @@
 use MountCalibration;
 use nalgebra::{Point3, Projective3};
+use serde::{Serialize, Serializer};
 use std::marker::PhantomData;
 use std::ops::Deref;
 
@@ -10,23 +11,32 @@ pub struct Point<C: CoordinateReferenceSystem> {
     point: Point3<f64>,
 }
 
+/// An axis-aligned bounding box.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct BoundingBox<C: CoordinateReferenceSystem> {
+    /// The minimum corner of the box.
+    pub min: Point<C>,
+    /// The maximum corner of the box.
+    pub max: Point<C>,
+}
+
 /// A marker trait for coordinate reference systems.
 pub trait CoordinateReferenceSystem {}
 
 /// The GLobal Coordinate System.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 pub struct Glcs {}
 
 /// The PRoject Coordinate System.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 pub struct Prcs {}
 
 /// The Scanner's Own Coordiate System.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 pub struct Socs {}
 
 /// The CaMera's Coordinate System.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 pub struct Cmcs {}
 
 impl Point<Glcs> {
@@ -250,6 +260,110 @@ impl Point<Cmcs> {
     }
 }
 
+impl<C: CoordinateReferenceSystem> BoundingBox<C> {
+    /// Returns the eight corners of this bounding box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{BoundingBox, Point};
+    /// let bounding_box = BoundingBox {
+    ///     min: Point::socs(0., 0., 0.),
+    ///     max: Point::socs(1., 2., 3.),
+    /// };
+    /// assert_eq!(8, bounding_box.corners().len());
+    /// ```
+    pub fn corners(&self) -> Vec<Point<C>> {
+        let mut corners = Vec::with_capacity(8);
+        for &x in &[self.min.x, self.max.x] {
+            for &y in &[self.min.y, self.max.y] {
+                for &z in &[self.min.z, self.max.z] {
+                    corners.push(Point3::new(x, y, z).into());
+                }
+            }
+        }
+        corners
+    }
+
+    /// Returns true if this bounding box contains the point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{BoundingBox, Point};
+    /// let bounding_box = BoundingBox {
+    ///     min: Point::socs(0., 0., 0.),
+    ///     max: Point::socs(1., 2., 3.),
+    /// };
+    /// assert!(bounding_box.contains(&Point::socs(0.5, 0.5, 0.5)));
+    /// assert!(!bounding_box.contains(&Point::socs(1.5, 0.5, 0.5)));
+    /// ```
+    pub fn contains(&self, point: &Point<C>) -> bool {
+        point.x >= self.min.x && point.y >= self.min.y && point.z >= self.min.z &&
+            point.x <= self.max.x && point.y <= self.max.y && point.z <= self.max.z
+    }
+
+    fn transform<D: CoordinateReferenceSystem>(&self, matrix: Projective3<f64>) -> BoundingBox<D> {
+        let mut corners = self.corners().into_iter().map(|corner| matrix * corner.deref());
+        let first = corners.next().expect("A bounding box always has corners");
+        let (min, max) = corners.fold((first, first), |(min, max), corner| {
+            (
+                Point3::new(min.x.min(corner.x), min.y.min(corner.y), min.z.min(corner.z)),
+                Point3::new(max.x.max(corner.x), max.y.max(corner.y), max.z.max(corner.z)),
+            )
+        });
+        BoundingBox {
+            min: min.into(),
+            max: max.into(),
+        }
+    }
+}
+
+impl BoundingBox<Socs> {
+    /// Converts this socs bounding box to the project coordinate system.
+    ///
+    /// The transformed box is axis-aligned in the project coordinate system, so it is a
+    /// conservative (i.e. possibly larger) bound on the original box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{BoundingBox, Point, Project};
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_positions.get("SP01").unwrap();
+    /// let bounding_box = BoundingBox {
+    ///     min: Point::socs(0., 0., 0.),
+    ///     max: Point::socs(1., 2., 3.),
+    /// };
+    /// let prcs = bounding_box.to_prcs(scan_position.sop);
+    /// ```
+    pub fn to_prcs(&self, sop: Projective3<f64>) -> BoundingBox<Prcs> {
+        self.transform(sop)
+    }
+}
+
+impl BoundingBox<Prcs> {
+    /// Converts this prcs bounding box to the global coordinate system.
+    ///
+    /// The transformed box is axis-aligned in the global coordinate system, so it is a
+    /// conservative (i.e. possibly larger) bound on the original box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{BoundingBox, Point, Project};
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let bounding_box = BoundingBox {
+    ///     min: Point::prcs(0., 0., 0.),
+    ///     max: Point::prcs(1., 2., 3.),
+    /// };
+    /// let glcs = bounding_box.to_glcs(project.pop);
+    /// ```
+    pub fn to_glcs(&self, pop: Projective3<f64>) -> BoundingBox<Glcs> {
+        self.transform(pop)
+    }
+}
+
 impl<C: CoordinateReferenceSystem> From<Point3<f64>> for Point<C> {
     fn from(point: Point3<f64>) -> Point<C> {
         Point {
@@ -272,6 +386,12 @@ impl<C: CoordinateReferenceSystem> PartialEq<Point<C>> for Point<C> {
     }
 }
 
+impl<C: CoordinateReferenceSystem> Serialize for Point<C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.point.serialize(serializer)
+    }
+}
+
 impl CoordinateReferenceSystem for Glcs {}
 impl CoordinateReferenceSystem for Prcs {}
 impl CoordinateReferenceSystem for Socs {}
@@ -288,6 +408,30 @@ mod tests {
         assert!(Point::cmcs(1., 1., -1.).is_behind_camera());
     }
 
+    #[test]
+    fn bounding_box_to_prcs() {
+        use nalgebra::{Translation3, UnitQuaternion, Vector3};
+        use std::f64::consts::FRAC_PI_4;
+
+        let bounding_box = BoundingBox {
+            min: Point::socs(-1., -1., -1.),
+            max: Point::socs(1., 1., 1.),
+        };
+        let sop = Projective3::identity() * Translation3::new(1., 2., 3.) *
+            UnitQuaternion::from_axis_angle(&Vector3::z_axis(), FRAC_PI_4);
+        let prcs = bounding_box.to_prcs(sop);
+        let half_diagonal = 2f64.sqrt();
+        assert_relative_eq!(1. - half_diagonal, prcs.min.x, epsilon = 1e-9);
+        assert_relative_eq!(2. - half_diagonal, prcs.min.y, epsilon = 1e-9);
+        assert_relative_eq!(2., prcs.min.z, epsilon = 1e-9);
+        assert_relative_eq!(1. + half_diagonal, prcs.max.x, epsilon = 1e-9);
+        assert_relative_eq!(2. + half_diagonal, prcs.max.y, epsilon = 1e-9);
+        assert_relative_eq!(4., prcs.max.z, epsilon = 1e-9);
+        for corner in bounding_box.corners() {
+            assert!(prcs.contains(&corner.to_prcs(sop)));
+        }
+    }
+
     #[test]
     fn roundtrip() {
         let glcs = Point::glcs(1., 2., 3.);
@@ -1,9 +1,17 @@
-use {CameraCalibration, Error, MountCalibration, Result, ScanPosition, utils};
+use {CameraCalibration, ControlPoint, Error, Glcs, MountCalibration, Node, NodeRef, ObjectRecord,
+     Point, Polyline, Prcs, ProjectWriter, ReflectorCalibration, ReflectorShape, RegistrationGraph,
+     Result, ScanPosition, State, utils};
+use camera_calibration::{OpenCv, Other};
+use chrono::NaiveDateTime;
 use element::Extension;
 use nalgebra::Projective3;
-use scan_position::{Image, Scan};
+use point::CoordinateReferenceSystem;
+use scan_position::{Atmosphere, DataFile, GeometryInfo, GnssPosition, Image, ImageTiePoint,
+                    MtaSettings, PolydataObject, Scan, TiePoint,
+                    UndistortedImage};
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use xmltree::Element;
 
 /// A RiSCAN Pro project.
@@ -23,14 +31,89 @@ pub struct Project {
     pub path: PathBuf,
     /// The camera calibrations, by name.
     pub camera_calibrations: BTreeMap<String, CameraCalibration>,
+    /// The project images, by name.
+    pub images: BTreeMap<String, ProjectImage>,
     /// The camera mount calibrations, by name.
     pub mount_calibrations: BTreeMap<String, MountCalibration>,
     /// The project's name.
     pub name: String,
+    /// The tilt mount calibrations, by name.
+    ///
+    /// The tilt is not yet applied when transforming from SOCS, i.e. SOCS is still the frame
+    /// registered by the scan position's SOP.
+    pub tilt_mount_calibrations: BTreeMap<String, ObjectRecord>,
+    /// The project's header information, e.g. its location and creation date.
+    pub info: ProjectInfo,
+    /// The scan configurations, i.e. preset scan patterns, by name.
+    pub scan_configurations: BTreeMap<String, ObjectRecord>,
+    /// The reflector calibrations, by name.
+    pub reflector_calibrations: BTreeMap<String, ReflectorCalibration>,
     /// The scan positions, by name.
     pub scan_positions: BTreeMap<String, ScanPosition>,
     /// The project's own position.
     pub pop: Projective3<f64>,
+    /// The control points in the project coordinate system, by name.
+    pub prcs_control_points: BTreeMap<String, ControlPoint<Prcs>>,
+    /// The control points in the global coordinate system, by name.
+    pub glcs_control_points: BTreeMap<String, ControlPoint<Glcs>>,
+    /// The tie objects in the project coordinate system, by name.
+    pub tie_objects: BTreeMap<String, ObjectRecord>,
+    /// The digitized planes, by name.
+    ///
+    /// Of the geometry objects only polylines are typed. For planes, spheres, cylinders, point
+    /// objects, and sections we have neither samples nor the dtd, so only their name, kind, and
+    /// state are read.
+    pub planes: BTreeMap<String, ObjectRecord>,
+    /// The digitized spheres, by name.
+    pub spheres: BTreeMap<String, ObjectRecord>,
+    /// The digitized cylinders, by name.
+    pub cylinders: BTreeMap<String, ObjectRecord>,
+    /// The digitized point objects, by name.
+    pub point_objects: BTreeMap<String, ObjectRecord>,
+    /// The digitized polylines in the project coordinate system, by name.
+    pub polylines: BTreeMap<String, Polyline<Prcs>>,
+    /// The sections, by name.
+    pub sections: BTreeMap<String, ObjectRecord>,
+}
+
+/// Header information about a project.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq)]
+pub struct ProjectInfo {
+    /// The project's latitude, in radians.
+    pub latitude: Option<f64>,
+    /// The project's longitude, in radians.
+    pub longitude: Option<f64>,
+    /// The type of the project's coordinate reference system, if recorded.
+    pub crs_type: Option<u32>,
+    /// The version of RiSCAN Pro that last wrote the project, e.g. `203009900`, if recorded.
+    pub app_version: Option<u32>,
+    /// The version of the project document format, e.g. `20300`, if recorded.
+    pub doc_version: Option<u32>,
+    /// The date the project was created, if recorded.
+    pub date_created: Option<NaiveDateTime>,
+    /// The date the project was last modified, if recorded.
+    pub date_modified: Option<NaiveDateTime>,
+}
+
+/// A project image.
+///
+/// Project images aren't attached to a scan position, e.g. a logo overlay.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ProjectImage {
+    /// The name of the image.
+    pub name: String,
+    /// The state of the image.
+    pub state: State,
+    /// The camera's own position when taking the image.
+    pub cop: Projective3<f64>,
+    /// Is the camera's own position frozen?
+    pub is_cop_frozen: bool,
+    /// The file name of the image.
+    pub file: String,
+    /// The reference to the image's camera calibration.
+    pub camera_calibration_ref: NodeRef,
+    /// The image tie points, by name.
+    pub tie_points: BTreeMap<String, ImageTiePoint>,
 }
 
 impl Project {
@@ -51,20 +134,35 @@ impl Project {
         let file = File::open(&path)?;
         let xml = Element::parse(file)?;
 
-        let camera_calibrations = xml.children("calibrations/camcalibs/camcalib_opencv")?
+        let camera_calibrations = xml.child("calibrations/camcalibs")?
+            .children
             .iter()
-            .map(|camcalib_opencv| {
-                let camera_calibration = CameraCalibration::from_element(camcalib_opencv)?;
-                Ok((camera_calibration.name.clone(), camera_calibration))
+            .map(|camcalib| {
+                let camera_calibration = CameraCalibration::from_element(camcalib)?;
+                Ok((camera_calibration.name().to_string(), camera_calibration))
             })
             .collect::<Result<BTreeMap<_, _>>>()?;
-        let mount_calibrations = xml.children("calibrations/mountcalibs/mountcalib")?
-            .iter()
+        let images = optional_children(&xml, "geometry_objects/images/image")
+            .into_iter()
+            .map(|image| {
+                let image = ProjectImage::from_element(image)?;
+                Ok((image.name.clone(), image))
+            })
+            .collect::<Result<BTreeMap<_, _>>>()?;
+        let mount_calibrations = optional_children(&xml, "calibrations/mountcalibs/mountcalib")
+            .into_iter()
             .map(|mountcalib| {
                 let mount_calibration = MountCalibration::from_element(mountcalib)?;
                 Ok((mount_calibration.name.clone(), mount_calibration))
             })
             .collect::<Result<BTreeMap<_, _>>>()?;
+        let reflector_calibrations = optional_children(&xml, "calibrations/reflcalibs/reflcalib")
+            .into_iter()
+            .map(|reflcalib| {
+                let reflector_calibration = ReflectorCalibration::from_element(reflcalib)?;
+                Ok((reflector_calibration.name.clone(), reflector_calibration))
+            })
+            .collect::<Result<BTreeMap<_, _>>>()?;
         let scan_positions = xml.children("scanpositions/scanposition")?
             .iter()
             .map(|scanposition| {
@@ -75,14 +173,83 @@ impl Project {
 
         Ok(Project {
             camera_calibrations: camera_calibrations,
+            images: images,
             mount_calibrations: mount_calibrations,
             name: xml.child("name")?.as_str()?.to_string(),
+            tilt_mount_calibrations: object_records(xml.child("calibrations/tiltmounts").ok()),
+            info: ProjectInfo::from_element(&xml)?,
+            scan_configurations: object_records(xml.child("collections/scanconfs").ok()),
+            reflector_calibrations: reflector_calibrations,
             scan_positions: scan_positions,
             path: path.canonicalize()?,
             pop: utils::parse_projective3(xml.child("pop/matrix")?.as_str()?)?,
+            prcs_control_points: control_points(&xml, "tpl_prcs/tp_prcs")?,
+            glcs_control_points: control_points(&xml, "tpl_glcs/tp_glcs")?,
+            tie_objects: object_records(xml.get_child("tol_prcs")),
+            planes: object_records(xml.child("geometry_objects/plane_objects").ok()),
+            spheres: object_records(xml.child("geometry_objects/sphere_objects").ok()),
+            cylinders: object_records(xml.child("geometry_objects/cylinder_objects").ok()),
+            point_objects: object_records(xml.child("geometry_objects/point_objects").ok()),
+            polylines: polylines(&xml)?,
+            sections: object_records(xml.child("geometry_objects/section_objects").ok()),
         })
     }
 
+    /// Saves this project's POP, SOPs, COPs, freeze flags, and calibrations to its rsp file.
+    ///
+    /// Only values that differ from the ones in the file are written, and every other element in
+    /// the file is kept byte-for-byte as it was. The previous file is copied to a backup, whose
+    /// path is returned. Objects that are not in the file, e.g. new scan positions, cannot be
+    /// saved.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use riscan_pro::Project;
+    /// let mut project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// project.scan_positions.get_mut("SP01").unwrap().is_frozen = true;
+    /// let backup_path = project.save().unwrap();
+    /// ```
+    pub fn save(&self) -> Result<PathBuf> {
+        let mut writer = ProjectWriter::from_path(&self.path)?;
+        writer.set_pop(&self.pop)?;
+        for (name, scan_position) in &self.scan_positions {
+            writer.set_sop(name, &scan_position.sop)?;
+            writer.set_scan_position_frozen(name, scan_position.is_frozen)?;
+            for (image_name, image) in &scan_position.images {
+                writer.set_cop(name, image_name, &image.cop)?;
+                writer.set_cop_frozen(name, image_name, image.is_cop_frozen)?;
+            }
+        }
+        for (name, image) in &self.images {
+            writer.set_project_image_cop(name, &image.cop)?;
+            writer.set_project_image_cop_frozen(name, image.is_cop_frozen)?;
+        }
+        for mount_calibration in self.mount_calibrations.values() {
+            writer.set_mount_calibration(mount_calibration)?;
+        }
+        for camera_calibration in self.camera_calibrations.values() {
+            if let Some(opencv) = camera_calibration.as_opencv() {
+                writer.set_camera_calibration(opencv)?;
+            }
+        }
+        writer.save()
+    }
+
+    /// Returns the graph of how this project's scan positions were registered to each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/southpole.rsp").unwrap();
+    /// let registration_graph = project.registration_graph();
+    /// assert_eq!(1, registration_graph.edges.len());
+    /// ```
+    pub fn registration_graph(&self) -> RegistrationGraph {
+        RegistrationGraph::new(self)
+    }
+
     /// Returns a scan position, as determined by the path.
     ///
     /// # Examples
@@ -121,61 +288,309 @@ impl Project {
             })
             .ok_or_else(|| Error::ScanPositionFromPath(path.as_ref().to_path_buf()))
     }
+
+    /// Returns the object referenced by a node reference.
+    ///
+    /// The first segment of the reference, the project name, is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{Node, NodeRef, Project};
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let noderef = NodeRef::new("/Project/CALIBRATIONS/REFLECTOR/RIEGL Flat 5 cm");
+    /// match project.resolve(&noderef).unwrap() {
+    ///     Node::ReflectorCalibration(reflector_calibration) => {
+    ///         assert_eq!("RIEGL Flat 5 cm", reflector_calibration.name)
+    ///     }
+    ///     _ => panic!("Should be a reflector calibration"),
+    /// }
+    /// let noderef = NodeRef::new("/Project/CALIBRATIONS/CAMERA/RIEGL Flat 5 cm");
+    /// assert!(project.resolve(&noderef).is_err());
+    /// ```
+    pub fn resolve<'a>(&'a self, noderef: &NodeRef) -> Result<Node<'a>> {
+        let segments = noderef.segments();
+        let node = match segments.as_slice() {
+            [_, "CALIBRATIONS", "CAMERA", name] => {
+                self.camera_calibrations.get(*name).map(Node::CameraCalibration)
+            }
+            [_, "CALIBRATIONS", "MOUNTING", name] => {
+                self.mount_calibrations.get(*name).map(Node::MountCalibration)
+            }
+            [_, "CALIBRATIONS", "REFLECTOR", name] => {
+                self.reflector_calibrations.get(*name).map(
+                    Node::ReflectorCalibration,
+                )
+            }
+            [_, "OBJECTS", "IMAGES", name] => self.images.get(*name).map(Node::ProjectImage),
+            [_, "OBJECTS", "POLYLINES", name] => self.polylines.get(*name).map(Node::Polyline),
+            [_, "COLLECTIONS", "CONFIGS", name] => {
+                self.scan_configurations.get(*name).map(Node::ScanConfiguration)
+            }
+            [_, "TPL (PRCS)"] => Some(Node::PrcsControlPoints(&self.prcs_control_points)),
+            [_, "TPL (PRCS)", name] => {
+                self.prcs_control_points.get(*name).map(Node::PrcsControlPoint)
+            }
+            [_, "TPL (GLCS)"] => Some(Node::GlcsControlPoints(&self.glcs_control_points)),
+            [_, "TPL (GLCS)", name] => {
+                self.glcs_control_points.get(*name).map(Node::GlcsControlPoint)
+            }
+            [_, "SCANS", scan_position] => {
+                self.scan_positions.get(*scan_position).map(Node::ScanPosition)
+            }
+            [_, "SCANS", scan_position, folder, name] => {
+                self.scan_positions.get(*scan_position).and_then(|scan_position| {
+                    match *folder {
+                        "SINGLESCANS" => {
+                            scan_position.scans.get(*name).map(|scan| {
+                                Node::Scan(scan_position, scan)
+                            })
+                        }
+                        "TIEPOINTSCANS" => {
+                            scan_position.tiepoint_scans.get(*name).map(|scan| {
+                                Node::TiepointScan(scan_position, scan)
+                            })
+                        }
+                        "POINTCLOUDS" => {
+                            scan_position.rdb2_point_clouds.get(*name).map(|point_cloud| {
+                                Node::PointCloud(scan_position, point_cloud)
+                            })
+                        }
+                        "SCANPOSIMAGES" => {
+                            scan_position.images.get(*name).map(|image| {
+                                Node::Image(scan_position, image)
+                            })
+                        }
+                        "TPL (SOCS)" => {
+                            scan_position.tie_points.get(*name).map(|tie_point| {
+                                Node::TiePoint(scan_position, tie_point)
+                            })
+                        }
+                        _ => None,
+                    }
+                })
+            }
+            [_, "SCANS", scan_position, "SCANPOSIMAGES", image, "TPL (IMAGE)", name] => {
+                self.scan_positions
+                    .get(*scan_position)
+                    .and_then(|scan_position| {
+                        scan_position.images.get(*image).map(|image| (scan_position, image))
+                    })
+                    .and_then(|(scan_position, image)| {
+                        image.tie_points.get(*name).map(|image_tie_point| {
+                            Node::ImageTiePoint(scan_position, image, image_tie_point)
+                        })
+                    })
+            }
+            _ => None,
+        };
+        node.ok_or_else(|| Error::DanglingNodeRef(noderef.path.clone()))
+    }
+}
+
+impl ProjectInfo {
+    /// Returns the project's latitude in degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let latitude = project.info.latitude_degrees().unwrap();
+    /// ```
+    pub fn latitude_degrees(&self) -> Option<f64> {
+        self.latitude.map(|latitude| latitude.to_degrees())
+    }
+
+    /// Returns the project's longitude in degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let longitude = project.info.longitude_degrees().unwrap();
+    /// ```
+    pub fn longitude_degrees(&self) -> Option<f64> {
+        self.longitude.map(|longitude| longitude.to_degrees())
+    }
+
+    fn from_element(element: &Element) -> Result<ProjectInfo> {
+        Ok(ProjectInfo {
+            latitude: match element.get_child("project_latitude") {
+                Some(latitude) => Some(latitude.parse_text()?),
+                None => None,
+            },
+            longitude: match element.get_child("project_longitude") {
+                Some(longitude) => Some(longitude.parse_text()?),
+                None => None,
+            },
+            crs_type: parse_optional(element, "crs_type")?,
+            app_version: parse_optional(element, "app_version")?,
+            doc_version: parse_optional(element, "doc_version")?,
+            date_created: optional_timestamp(element, "date_created")?,
+            date_modified: optional_timestamp(element, "date_modified")?,
+        })
+    }
+}
+
+impl ProjectImage {
+    /// Finds and returns this image's camera calibration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{NodeRef, Project};
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let mut image = project.images.get("Logo").unwrap().clone();
+    /// image.camera_calibration(&project).unwrap();
+    /// image.camera_calibration_ref = NodeRef::new("/Project/CALIBRATIONS/CAMERA/Not a camera");
+    /// assert!(image.camera_calibration(&project).is_err());
+    /// ```
+    pub fn camera_calibration<'a>(&self, project: &'a Project) -> Result<&'a CameraCalibration> {
+        match project.resolve(&self.camera_calibration_ref) {
+            Ok(Node::CameraCalibration(camera_calibration)) => Ok(camera_calibration),
+            _ => Err(Error::MissingCameraCalibration(
+                self.camera_calibration_ref.path.clone(),
+            )),
+        }
+    }
+
+    fn from_element(element: &Element) -> Result<ProjectImage> {
+        Ok(ProjectImage {
+            name: element.child("name")?.as_str()?.to_string(),
+            state: state(element),
+            cop: utils::parse_projective3(element.child("cop/matrix")?.as_str()?)?,
+            is_cop_frozen: element.child("cop/freeze")?.as_str()? == "1",
+            file: element.child("file")?.as_str()?.to_string(),
+            camera_calibration_ref: element.child("camcalib_ref")?.noderef()?,
+            tie_points: image_tie_points(element)?,
+        })
+    }
 }
 
 impl CameraCalibration {
     fn from_element(element: &Element) -> Result<CameraCalibration> {
-        let version = element.child("version")?.as_str()?;
-        if version == "2" {
-            Ok(CameraCalibration {
-                name: element.child("name")?.as_str()?.to_string(),
-                cx: element.child("internal_opencv/cx")?.parse_text()?,
-                cy: element.child("internal_opencv/cy")?.parse_text()?,
-                fx: element.child("internal_opencv/fx")?.parse_text()?,
-                fy: element.child("internal_opencv/fy")?.parse_text()?,
-                k1: element.child("internal_opencv/k1")?.parse_text()?,
-                k2: element.child("internal_opencv/k2")?.parse_text()?,
-                k3: element.child("internal_opencv/k3")?.parse_text()?,
-                k4: element.child("internal_opencv/k4")?.parse_text()?,
-                p1: element.child("internal_opencv/p1")?.parse_text()?,
-                p2: element.child("internal_opencv/p2")?.parse_text()?,
-                tan_max_horz: element.child("angle_extents/tan_max_horz")?.parse_text()?,
-                tan_max_vert: element.child("angle_extents/tan_max_vert")?.parse_text()?,
-                tan_min_horz: element.child("angle_extents/tan_min_horz")?.parse_text()?,
-                tan_min_vert: element.child("angle_extents/tan_min_vert")?.parse_text()?,
-                width: element.child("intrinsic_opencv/nx")?.parse_text()?,
-                height: element.child("intrinsic_opencv/ny")?.parse_text()?,
-            })
+        if element.name == "camcalib_opencv" {
+            Ok(CameraCalibration::OpenCv(Box::new(OpenCv::from_element(element)?)))
         } else {
-            Err(Error::CameraCalibrationVersion(version.to_string()))
+            Ok(CameraCalibration::Other(Other {
+                name: element.child("name")?.as_str()?.to_string(),
+                state: state(element),
+                kind: element.name.clone(),
+            }))
         }
     }
 }
 
+impl OpenCv {
+    fn from_element(element: &Element) -> Result<OpenCv> {
+        // Versions 0 and 1 share the version 2 layout, see the `OpenCv` docs.
+        let version = match element.child("version")?.as_str()? {
+            "0" => 0,
+            "1" => 1,
+            "2" => 2,
+            version => return Err(Error::CameraCalibrationVersion(version.to_string())),
+        };
+        Ok(OpenCv {
+            version: version,
+            name: element.child("name")?.as_str()?.to_string(),
+            state: state(element),
+            camera_model: element.get_child("cameramodel").map(text),
+            camera_serial_number: element.get_child("cameraserialnumber").map(text),
+            lens_model: element.get_child("lensmodel").map(text),
+            lens_serial_number: element.get_child("lensserialnumber").map(text),
+            settings: element.get_child("settings").map(text),
+            cx: element.child("internal_opencv/cx")?.parse_text()?,
+            cy: element.child("internal_opencv/cy")?.parse_text()?,
+            fx: element.child("internal_opencv/fx")?.parse_text()?,
+            fy: element.child("internal_opencv/fy")?.parse_text()?,
+            k1: element.child("internal_opencv/k1")?.parse_text()?,
+            k2: element.child("internal_opencv/k2")?.parse_text()?,
+            k3: element.child("internal_opencv/k3")?.parse_text()?,
+            k4: element.child("internal_opencv/k4")?.parse_text()?,
+            p1: element.child("internal_opencv/p1")?.parse_text()?,
+            p2: element.child("internal_opencv/p2")?.parse_text()?,
+            tan_max_horz: element.child("angle_extents/tan_max_horz")?.parse_text()?,
+            tan_max_vert: element.child("angle_extents/tan_max_vert")?.parse_text()?,
+            tan_min_horz: element.child("angle_extents/tan_min_horz")?.parse_text()?,
+            tan_min_vert: element.child("angle_extents/tan_min_vert")?.parse_text()?,
+            dx: parse_optional(element.child("intrinsic_opencv")?, "dx")?,
+            dy: parse_optional(element.child("intrinsic_opencv")?, "dy")?,
+            width: element.child("intrinsic_opencv/nx")?.parse_text()?,
+            height: element.child("intrinsic_opencv/ny")?.parse_text()?,
+        })
+    }
+}
+
 impl MountCalibration {
     fn from_element(element: &Element) -> Result<MountCalibration> {
         Ok(MountCalibration {
             name: element.child("name")?.as_str()?.to_string(),
+            state: state(element),
             matrix: utils::parse_projective3(element.child("matrix")?.as_str()?)?,
+            is_adjusted: match element.get_child("adjusted") {
+                Some(adjusted) => adjusted.as_str()? == "1",
+                None => false,
+            },
+        })
+    }
+}
+
+impl ReflectorCalibration {
+    fn from_element(element: &Element) -> Result<ReflectorCalibration> {
+        Ok(ReflectorCalibration {
+            name: element.child("name")?.as_str()?.to_string(),
+            state: state(element),
+            color: element.child("refl_color")?.parse_text()?,
+            constant: element.child("refl_const")?.parse_text()?,
+            shape: match element.child("refl_type")?.as_str()? {
+                "Disc" => ReflectorShape::Disc {
+                    diameter: element.child("v_disc_diameter")?.parse_text()?,
+                    width: element.child("v_disc_width")?.parse_text()?,
+                },
+                "Cylinder" => ReflectorShape::Cylinder {
+                    diameter: element.child("v_cylinder_diameter")?.parse_text()?,
+                    height: element.child("v_cylinder_height")?.parse_text()?,
+                },
+                reflector_type => ReflectorShape::Other(reflector_type.to_string()),
+            },
         })
     }
 }
 
 impl ScanPosition {
     fn from_element(element: &Element) -> Result<ScanPosition> {
+        let name = element.child("name")?.as_str()?.to_string();
         Ok(ScanPosition {
-            name: element.child("name")?.as_str()?.to_string(),
-            images: element
-                .children("scanposimages/scanposimage")?
-                .iter()
+            name: name.clone(),
+            images: optional_children(element, "scanposimages/scanposimage")
+                .into_iter()
                 .map(|scanposimage| {
-                    let image = Image::from_element(scanposimage)?;
+                    let image = Image::from_element(scanposimage, &name)?;
                     Ok((image.name.clone(), image))
                 })
                 .collect::<Result<_>>()?,
-            scans: element
-                .children("singlescans/scan")?
-                .iter()
+            polydata_objects: polydata_objects(element, &name)?,
+            scan_sequences: data_files(element, "scansequences", "SCANSEQUENCES", &name)?,
+            profile_scans: data_files(element, "profilescans", "PROFILESCANS", &name)?,
+            rdb2_point_clouds: data_files(element, "rdb2pointclouds", "POINTCLOUDS", &name)?,
+            undistorted_images: optional_children(element, "scanposundistimages/scanposundistimage")
+                .into_iter()
+                .map(|scanposundistimage| {
+                    let image = UndistortedImage::from_element(scanposundistimage, &name)?;
+                    Ok((image.name.clone(), image))
+                })
+                .collect::<Result<_>>()?,
+            scans: optional_children(element, "singlescans/scan")
+                .into_iter()
+                .map(|scan| {
+                    let scan = Scan::from_element(scan)?;
+                    Ok((scan.name.clone(), scan))
+                })
+                .collect::<Result<_>>()?,
+            tiepoint_scans: optional_children(element, "tiepointscans/scan")
+                .into_iter()
                 .map(|scan| {
                     let scan = Scan::from_element(scan)?;
                     Ok((scan.name.clone(), scan))
@@ -183,6 +598,62 @@ impl ScanPosition {
                 .collect::<Result<_>>()?,
             sop: utils::parse_projective3(element.child("sop/matrix")?.as_str()?)?,
             is_frozen: element.child("sop/freeze")?.as_str()? == "1",
+            tilt_mount_position: match element.get_child("tiltmount_position") {
+                Some(tiltmount_position) => {
+                    match tiltmount_position.parse_text()? {
+                        -1 => None,
+                        position => Some(position),
+                    }
+                }
+                None => None,
+            },
+            gnss_position: match element.get_child("position_enabled") {
+                Some(position_enabled) if position_enabled.as_str()? == "1" => {
+                    Some(GnssPosition::from_element(element)?)
+                }
+                _ => None,
+            },
+            atmosphere: match element.get_child("ppm_air_pressure") {
+                Some(_) => Some(Atmosphere::from_element(element)?),
+                None => None,
+            },
+            pose_estimations: object_records(element.get_child("poseestimations")),
+            tie_points: optional_children(element, "tpl_socs/tp_socs")
+                .into_iter()
+                .map(|tp_socs| {
+                    let tie_point = TiePoint::from_element(tp_socs)?;
+                    Ok((tie_point.name.clone(), tie_point))
+                })
+                .collect::<Result<_>>()?,
+            tie_objects: object_records(element.get_child("tol_socs")),
+        })
+    }
+}
+
+impl GnssPosition {
+    fn from_element(element: &Element) -> Result<GnssPosition> {
+        use nalgebra::Point3;
+        Ok(GnssPosition {
+            position: Point3::from(utils::parse_vector3(
+                element.child("position_vector")?.as_str()?,
+            )?),
+            accuracy: element.child("position_accuracy")?.parse_text()?,
+            coordinate_system: element.child("position_coordsys")?.parse_text()?,
+        })
+    }
+}
+
+impl Atmosphere {
+    fn from_element(element: &Element) -> Result<Atmosphere> {
+        Ok(Atmosphere {
+            air_pressure: element.child("ppm_air_pressure")?.parse_text()?,
+            air_temperature: element.child("ppm_air_temperature")?.parse_text()?,
+            moisture_pressure: element.child("ppm_moisture_pressure")?.parse_text()?,
+            instrument: element.get_child("ppm_instrument").map(text),
+            total_geometric_correction: parse_optional(
+                element,
+                "ppm_total_geometric_correction",
+            )?,
         })
     }
 }
@@ -192,24 +663,432 @@ impl Scan {
         Ok(Scan {
             name: element.child("name")?.as_str()?.to_string(),
             file: element.child("file")?.as_str()?.to_string(),
+            state: state(element),
             theta_count: element.child("theta_count")?.parse_text()?,
             phi_count: element.child("phi_count")?.parse_text()?,
+            phi_start: element.child("phi_start")?.parse_text()?,
+            phi_delta: element.child("phi_delta")?.parse_text()?,
+            theta_start: element.child("theta_start")?.parse_text()?,
+            theta_delta: element.child("theta_delta")?.parse_text()?,
+            instrument: element.get_child("instrument").map(text),
+            serial_number: element.get_child("serialnumber").map(text),
+            measurement_program: element.get_child("measurementprogram").map(text),
+            laser_clock: parse_optional(element, "laser_clock")?,
+            beam_focus: parse_optional(element, "beamfocus")?,
+            mta: MtaSettings::from_element(element)?,
+            frame_count: parse_optional(element, "frame_count")?,
+            time: match element.get_child("time").and_then(|time| time.text.as_ref()) {
+                Some(time) => Some(utils::parse_duration(time)?),
+                None => None,
+            },
+            geometry_info: match element.get_child("geometryinfo") {
+                Some(geometryinfo) => Some(GeometryInfo::from_element(geometryinfo)?),
+                None => None,
+            },
+            scan_configuration_ref: optional_noderef(element, "scanconf_ref")?,
+        })
+    }
+}
+
+impl GeometryInfo {
+    fn from_element(element: &Element) -> Result<GeometryInfo> {
+        use nalgebra::Point3;
+        Ok(GeometryInfo {
+            min_cart: Point3::from(utils::parse_vector3(element.child("mincart")?.as_str()?)?)
+                .into(),
+            max_cart: Point3::from(utils::parse_vector3(element.child("maxcart")?.as_str()?)?)
+                .into(),
+            min_polar: utils::parse_vector3(element.child("minpolar")?.as_str()?)?,
+            max_polar: utils::parse_vector3(element.child("maxpolar")?.as_str()?)?,
+            intensity_min: element.child("intmin")?.parse_text()?,
+            intensity_max: element.child("intmax")?.parse_text()?,
+            reflectance_min: parse_optional(element, "reflmin")?,
+            reflectance_max: parse_optional(element, "reflmax")?,
+            is_cart_valid: element.child("valid_cart")?.as_str()? == "1",
+            is_intensity_valid: element.child("valid_int")?.as_str()? == "1",
+            is_reflectance_valid: match element.get_child("valid_refl") {
+                Some(valid_refl) => Some(valid_refl.as_str()? == "1"),
+                None => None,
+            },
+        })
+    }
+}
+
+impl MtaSettings {
+    fn from_element(element: &Element) -> Result<MtaSettings> {
+        let autocalc_enabled: Option<i32> = parse_optional(element, "mta_autocalc_enabled")?;
+        let estimated_max_range: Option<f64> = parse_optional(element, "mta_estmax_scanrange")?;
+        let fixed_zone: Option<i32> = parse_optional(element, "mta_manual_fixed_zone")?;
+        Ok(MtaSettings {
+            autocalc_enabled: autocalc_enabled
+                .filter(|&autocalc_enabled| autocalc_enabled >= 0)
+                .map(|autocalc_enabled| autocalc_enabled != 0),
+            estimated_max_range: estimated_max_range.filter(|&range| range >= 0.),
+            fixed_zone: fixed_zone
+                .filter(|&fixed_zone| fixed_zone >= 0)
+                .map(|fixed_zone| fixed_zone as u32),
+            range_min: parse_optional(element, "mta_manual_range_min")?,
+            range_max: parse_optional(element, "mta_manual_range_max")?,
+        })
+    }
+}
+
+impl TiePoint {
+    fn from_element(element: &Element) -> Result<TiePoint> {
+        use nalgebra::Point3;
+        Ok(TiePoint {
+            name: element.child("name")?.as_str()?.to_string(),
+            position: Point3::from(utils::parse_vector3(element.child("vector")?.as_str()?)?)
+                .into(),
+            direction: utils::parse_vector3(element.child("direction")?.as_str()?)?,
+            accuracy: utils::parse_vector3(element.child("accuracy")?.as_str()?)?,
+            intensity: element.child("intensity")?.parse_text()?,
+            reflectivity: element.child("reflectivity")?.parse_text()?,
+            pixels: element.child("pixels")?.parse_text()?,
+            is_active: element.child("active")?.as_str()? == "1",
+            reflector_calibration_ref: optional_noderef(element, "refl_ref")?,
+            tiepoint_scan_ref: optional_noderef(element, "finescan_ref")?,
+            linked: optional_noderef(element, "linked_ref")?,
+            referrers: referrers(element)?,
         })
     }
 }
 
 impl Image {
-    fn from_element(element: &Element) -> Result<Image> {
+    fn from_element(element: &Element, scan_position_name: &str) -> Result<Image> {
         Ok(Image {
             name: element.child("name")?.as_str()?.to_string(),
+            state: state(element),
+            scan_position_name: scan_position_name.to_string(),
+            cop: utils::parse_projective3(element.child("cop/matrix")?.as_str()?)?,
+            is_cop_frozen: element.child("cop/freeze")?.as_str()? == "1",
+            file: element.get_child("file").and_then(|file| file.text.clone()),
+            phi: parse_optional(element, "phi")?,
+            theta: parse_optional(element, "theta")?,
+            raw_lambda: parse_optional(element, "raw_lambda")?.and_then(not_nan),
+            raw_phi: parse_optional(element, "raw_phi")?.and_then(not_nan),
+            camera_calibration_ref: element.child("camcalib_ref")?.noderef()?,
+            mount_calibration_ref: element.child("mountcalib_ref")?.noderef()?,
+            tie_points: image_tie_points(element)?,
+        })
+    }
+}
+
+impl PolydataObject {
+    fn from_element(
+        element: &Element,
+        scan_position_name: &str,
+        polydata_folder: &str,
+    ) -> Result<PolydataObject> {
+        Ok(PolydataObject {
+            name: element.child("name")?.as_str()?.to_string(),
+            state: state(element),
+            scan_position_name: scan_position_name.to_string(),
+            polydata_folder: polydata_folder.to_string(),
+            folder: match element.attributes.get("fold") {
+                Some(fold) => fold.clone(),
+                None => element.child("name")?.as_str()?.to_string(),
+            },
+            file: element.child("file")?.as_str()?.to_string(),
+            color: element.child("color")?.parse_text()?,
+            is_used_for_msa: element.child("use_for_msa")?.as_str()? == "1",
+            geometry_info: match element.get_child("geometryinfo") {
+                Some(geometryinfo) => Some(GeometryInfo::from_element(geometryinfo)?),
+                None => None,
+            },
+            parameters: element
+                .children
+                .iter()
+                .filter(|child| {
+                    !["name", "file", "color", "use_for_msa", "geometryinfo"]
+                        .contains(&child.name.as_str())
+                })
+                .map(|child| (child.name.clone(), text(child)))
+                .collect(),
+        })
+    }
+}
+
+impl DataFile {
+    fn from_element(element: &Element, scan_position_name: &str, folder: &str) -> Result<DataFile> {
+        Ok(DataFile {
+            name: element.child("name")?.as_str()?.to_string(),
+            state: state(element),
+            scan_position_name: scan_position_name.to_string(),
+            folder: folder.to_string(),
+            file: element.child("file")?.as_str()?.to_string(),
+        })
+    }
+}
+
+impl ObjectRecord {
+    fn from_element(element: &Element) -> ObjectRecord {
+        ObjectRecord {
+            name: element.attributes.get("name").cloned().unwrap_or_default(),
+            kind: element.attributes.get("kind").cloned().unwrap_or_default(),
+            state: state(element),
+        }
+    }
+}
+
+impl UndistortedImage {
+    fn from_element(element: &Element, scan_position_name: &str) -> Result<UndistortedImage> {
+        Ok(UndistortedImage {
+            name: element.child("name")?.as_str()?.to_string(),
+            state: state(element),
+            scan_position_name: scan_position_name.to_string(),
             cop: utils::parse_projective3(element.child("cop/matrix")?.as_str()?)?,
-            camera_calibration_name: element.child("camcalib_ref")?.noderef()?.to_string(),
-            mount_calibration_name: element.child("mountcalib_ref")?.noderef()?.to_string(),
+            file: element.child("file")?.as_str()?.to_string(),
+            mount_calibration_ref: element.child("mountcalib_ref")?.noderef()?,
+            source_image_ref: element.child("source_image_ref")?.noderef()?,
+            tie_points: image_tie_points(element)?,
         })
     }
 }
 
-fn rsp_path<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
+impl ImageTiePoint {
+    fn from_element(element: &Element) -> Result<ImageTiePoint> {
+        Ok(ImageTiePoint {
+            name: element.child("name")?.as_str()?.to_string(),
+            u: element.child("u")?.parse_text()?,
+            v: element.child("v")?.parse_text()?,
+            size: element.child("size")?.parse_text()?,
+            is_active: element.child("active")?.as_str()? == "1",
+            reflector_calibration_ref: optional_noderef(element, "refl_ref")?,
+            linked: optional_noderef(element, "linked_ref")?,
+        })
+    }
+}
+
+impl<C: CoordinateReferenceSystem> ControlPoint<C> {
+    fn from_element(element: &Element) -> Result<ControlPoint<C>> {
+        use nalgebra::Point3;
+        Ok(ControlPoint {
+            name: element.child("name")?.as_str()?.to_string(),
+            position: Point3::from(utils::parse_vector3(element.child("vector")?.as_str()?)?)
+                .into(),
+            height: element.child("height")?.parse_text()?,
+            is_active: element.child("active")?.as_str()? == "1",
+            is_fixed: match element.get_child("fixed") {
+                Some(fixed) => fixed.as_str()? == "1",
+                None => false,
+            },
+            weight: match element.get_child("weight") {
+                Some(weight) => Some(weight.parse_text()?),
+                None => None,
+            },
+            reflector_calibration_ref: optional_noderef(element, "refl_ref")?,
+            linked: optional_noderef(element, "linked_ref")?,
+            referrers: referrers(element)?,
+        })
+    }
+}
+
+impl Polyline<Prcs> {
+    fn from_element(element: &Element) -> Result<Polyline<Prcs>> {
+        Ok(Polyline {
+            name: element.child("name")?.as_str()?.to_string(),
+            points: element
+                .children_named("point")?
+                .into_iter()
+                .map(point)
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+fn state(element: &Element) -> State {
+    let is_deleted = element
+        .attributes
+        .get("states")
+        .map(|states| states.split(',').any(|state| state.trim() == "deleted"))
+        .unwrap_or(false);
+    if is_deleted {
+        State::Deleted { date: element.attributes.get("date_deleted").cloned() }
+    } else {
+        State::Default
+    }
+}
+
+fn text(element: &Element) -> String {
+    element.text.clone().unwrap_or_default()
+}
+
+/// Parses the text of a child element, or returns `None` if there is no such child.
+fn parse_optional<T>(element: &Element, name: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    Error: From<<T as FromStr>::Err>,
+{
+    match element.get_child(name) {
+        Some(child) => Ok(Some(child.parse_text()?)),
+        None => Ok(None),
+    }
+}
+
+/// Parses the timestamp in the named child, treating a missing or empty child as `None`.
+fn optional_timestamp(element: &Element, name: &str) -> Result<Option<NaiveDateTime>> {
+    match element.get_child(name).and_then(|child| child.text.as_ref()) {
+        Some(text) => Ok(Some(utils::parse_timestamp(text)?)),
+        None => Ok(None),
+    }
+}
+
+fn optional_noderef(element: &Element, name: &str) -> Result<Option<NodeRef>> {
+    match element.get_child(name) {
+        Some(child) => Ok(Some(child.noderef()?)),
+        None => Ok(None),
+    }
+}
+
+fn referrers(element: &Element) -> Result<Vec<NodeRef>> {
+    element
+        .children_named("referrer_ref")?
+        .into_iter()
+        .map(|referrer_ref| referrer_ref.noderef())
+        .collect()
+}
+
+fn not_nan(value: f64) -> Option<f64> {
+    if value.is_nan() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Returns the elements at the path, e.g. `tpl_socs/tp_socs`, skipping any other children.
+///
+/// Unlike `Extension::children_named`, a missing container is treated as empty.
+fn optional_children<'a>(element: &'a Element, path: &str) -> Vec<&'a Element> {
+    element.children_named(path).unwrap_or_default()
+}
+
+fn image_tie_points(element: &Element) -> Result<BTreeMap<String, ImageTiePoint>> {
+    optional_children(element, "tpl_image/tp_image")
+        .into_iter()
+        .map(|tp_image| {
+            let tie_point = ImageTiePoint::from_element(tp_image)?;
+            Ok((tie_point.name.clone(), tie_point))
+        })
+        .collect()
+}
+
+fn control_points<C: CoordinateReferenceSystem>(
+    element: &Element,
+    path: &str,
+) -> Result<BTreeMap<String, ControlPoint<C>>> {
+    optional_children(element, path)
+        .into_iter()
+        .map(|element| {
+            let control_point = ControlPoint::from_element(element)?;
+            Ok((control_point.name.clone(), control_point))
+        })
+        .collect()
+}
+
+/// Parses the objects in a container whose layout we have not seen, e.g. `tol_prcs`, by name.
+///
+/// Only children with a `kind` attribute are objects, which skips e.g. the `active` flag of a tie
+/// object list. A missing container is treated as empty.
+fn object_records(container: Option<&Element>) -> BTreeMap<String, ObjectRecord> {
+    match container {
+        Some(container) => {
+            container
+                .children
+                .iter()
+                .filter(|child| child.attributes.contains_key("kind"))
+                .map(|child| {
+                    let object_record = ObjectRecord::from_element(child);
+                    (object_record.name.clone(), object_record)
+                })
+                .collect()
+        }
+        None => BTreeMap::new(),
+    }
+}
+
+/// Parses the children of a scan position's data file container, e.g. `rdb2pointclouds`.
+///
+/// The folder is taken from the container's `fold` attribute. Missing containers are treated as
+/// empty.
+/// Returns a container's `fold` attribute, the directory that holds its objects' data.
+fn fold<'a>(container: &'a Element, default: &'a str) -> &'a str {
+    container
+        .attributes
+        .get("fold")
+        .map(|fold| fold.as_str())
+        .unwrap_or(default)
+}
+
+fn polydata_objects(
+    element: &Element,
+    scan_position_name: &str,
+) -> Result<BTreeMap<String, PolydataObject>> {
+    match element.get_child("polydata_objects") {
+        Some(container) => {
+            let polydata_folder = fold(container, "POLYDATA");
+            optional_children(container, "polydata_object")
+                .into_iter()
+                .map(|child| {
+                    let polydata_object =
+                        PolydataObject::from_element(child, scan_position_name, polydata_folder)?;
+                    Ok((polydata_object.name.clone(), polydata_object))
+                })
+                .collect()
+        }
+        None => Ok(BTreeMap::new()),
+    }
+}
+
+fn data_files(
+    element: &Element,
+    container: &str,
+    default_folder: &str,
+    scan_position_name: &str,
+) -> Result<BTreeMap<String, DataFile>> {
+    match element.get_child(container) {
+        Some(container) => {
+            let folder = fold(container, default_folder);
+            container
+                .children
+                .iter()
+                .map(|child| {
+                    let data_file = DataFile::from_element(child, scan_position_name, folder)?;
+                    Ok((data_file.name.clone(), data_file))
+                })
+                .collect()
+        }
+        None => Ok(BTreeMap::new()),
+    }
+}
+
+fn point<C: CoordinateReferenceSystem>(element: &Element) -> Result<Point<C>> {
+    use nalgebra::Point3;
+    Ok(Point3::from(utils::parse_vector3(element.as_str()?)?).into())
+}
+
+/// Parses the polylines in `geometry_objects/polyline_objects`, keyed by their names.
+///
+/// A missing container is treated as empty.
+fn polylines(element: &Element) -> Result<BTreeMap<String, Polyline<Prcs>>> {
+    match element.child("geometry_objects/polyline_objects").ok() {
+        Some(container) => {
+            container
+                .children
+                .iter()
+                .filter(|child| child.name == "polyline_object")
+                .map(|child| {
+                    let polyline = Polyline::from_element(child)?;
+                    Ok((polyline.name.clone(), polyline))
+                })
+                .collect()
+        }
+        None => Ok(BTreeMap::new()),
+    }
+}
+
+pub(crate) fn rsp_path<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
     if let Some(extension) = path.as_ref().extension() {
         let mut path_buf = path.as_ref().to_path_buf();
         if extension == "rsp" {
@@ -236,6 +1115,8 @@ fn rsp_path<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use Point;
+    use std::ops::Deref;
 
     #[test]
     fn from_path() {
@@ -245,6 +1126,187 @@ mod tests {
         assert!(Project::from_path("data").is_err());
     }
 
+    #[test]
+    fn info() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let info = project.info;
+        assert_eq!(0.5187102610797655, info.latitude.unwrap());
+        assert_eq!(-1.6639430809579571, info.longitude.unwrap());
+        assert_relative_eq!(29.72, info.latitude_degrees().unwrap(), epsilon = 1e-2);
+        assert_relative_eq!(-95.34, info.longitude_degrees().unwrap(), epsilon = 1e-2);
+        assert_eq!(Some(1), info.crs_type);
+        assert_eq!(Some(203009900), info.app_version);
+        assert_eq!(Some(20300), info.doc_version);
+        assert_eq!(
+            "2015-11-20 08:45:54.085",
+            info.date_created.unwrap().to_string()
+        );
+        assert_eq!(
+            "2016-08-25 15:21:06.815",
+            info.date_modified.unwrap().to_string()
+        );
+
+        let project = Project::from_path("data/southpole.rsp").unwrap();
+        assert_eq!(None, project.info.latitude);
+        assert_eq!(None, project.info.longitude_degrees());
+        assert_eq!(Some(20600), project.info.doc_version);
+    }
+
+    #[test]
+    fn save() {
+        use std::fs;
+
+        let directory = ::std::env::temp_dir()
+            .join(format!("riscan-pro-save-{}", ::std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("project.rsp");
+        fs::copy("data/project.RiSCAN/project.rsp", &path).unwrap();
+        let original = fs::read_to_string(&path).unwrap();
+
+        let mut project = Project::from_path(&path).unwrap();
+        let backup_path = project.save().unwrap();
+        assert_eq!(original, fs::read_to_string(&path).unwrap());
+        assert_eq!(original, fs::read_to_string(&backup_path).unwrap());
+
+        project.pop = Projective3::identity();
+        {
+            let scan_position = project.scan_positions.get_mut("SP01").unwrap();
+            scan_position.sop = Projective3::identity();
+            scan_position.is_frozen = !scan_position.is_frozen;
+            let image = scan_position.images.get_mut("SP01 - Image001").unwrap();
+            image.cop = Projective3::identity();
+            image.is_cop_frozen = !image.is_cop_frozen;
+        }
+        {
+            let image = project.images.get_mut("Logo").unwrap();
+            image.is_cop_frozen = !image.is_cop_frozen;
+        }
+        for mount_calibration in project.mount_calibrations.values_mut() {
+            mount_calibration.is_adjusted = true;
+        }
+        for camera_calibration in project.camera_calibrations.values_mut() {
+            if let CameraCalibration::OpenCv(ref mut opencv) = *camera_calibration {
+                opencv.k1 = 0.;
+            }
+        }
+        let backup_path = project.save().unwrap();
+        assert_eq!(original, fs::read_to_string(&backup_path).unwrap());
+        assert_eq!(project, Project::from_path(&path).unwrap());
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(original.lines().count(), text.lines().count());
+        let changed = original
+            .lines()
+            .zip(text.lines())
+            .filter(|&(a, b)| a != b)
+            .count();
+        assert_eq!(8, changed);
+        assert!(!path.with_extension("rsp.tmp").exists());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn resolve() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let image = project.images.get("Logo").unwrap();
+        let xml = {
+            use std::fs::File;
+            Element::parse(File::open("data/project.RiSCAN/project.rsp").unwrap()).unwrap()
+        };
+        let noderef = xml.child("geometry_objects/images/image/camcalib_ref")
+            .unwrap()
+            .noderef()
+            .unwrap();
+        assert_eq!(
+            Node::CameraCalibration(image.camera_calibration(&project).unwrap()),
+            project.resolve(&noderef).unwrap()
+        );
+
+        let scan_position = project.scan_positions.get("SP01").unwrap();
+        let tie_point = scan_position.tie_points.get("tp001").unwrap();
+        let scan = tie_point.tiepoint_scan(&project).unwrap().unwrap();
+        let noderef = NodeRef::new(format!(
+            "/2.2.1_Test_InfraTec_VZ1000_Calibration/SCANS/SP01/TIEPOINTSCANS/{}",
+            scan.name
+        ));
+        assert_eq!(Node::TiepointScan(scan_position, scan), project.resolve(&noderef).unwrap());
+        let noderef = NodeRef::new("/2.2.1_Test_InfraTec_VZ1000_Calibration/SCANS/SP01");
+        assert_eq!(Node::ScanPosition(scan_position), project.resolve(&noderef).unwrap());
+        let noderef =
+            NodeRef::new("/2.2.1_Test_InfraTec_VZ1000_Calibration/SCANS/SP01/TPL (SOCS)/tp001");
+        assert_eq!(Node::TiePoint(scan_position, tie_point), project.resolve(&noderef).unwrap());
+
+        for path in vec![
+            "/2.2.1_Test_InfraTec_VZ1000_Calibration/CALIBRATIONS/REFLECTOR/Infratec_VarioCAM_HD_15mm_11-16-2015_Preston",
+            "/2.2.1_Test_InfraTec_VZ1000_Calibration/SCANS/SP03",
+            "/2.2.1_Test_InfraTec_VZ1000_Calibration/SCANS/SP01/TIEPOINTSCANS/not-a-scan",
+            "/2.2.1_Test_InfraTec_VZ1000_Calibration/OBJECTS/POLYLINES/polyline",
+            "/2.2.1_Test_InfraTec_VZ1000_Calibration/SCANS/SP01/SCANPOSIMAGES/SP01 - Image001/TPL (IMAGE)/tp999",
+            "",
+        ]
+        {
+            assert!(project.resolve(&NodeRef::new(path)).is_err());
+        }
+
+        let image = &scan_position.images["SP01 - Image004"];
+        let noderef = NodeRef::new(
+            "/2.2.1_Test_InfraTec_VZ1000_Calibration/SCANS/SP01/SCANPOSIMAGES/SP01 - Image004/TPL (IMAGE)/tp006",
+        );
+        assert_eq!(
+            Node::ImageTiePoint(scan_position, image, &image.tie_points["tp006"]),
+            project.resolve(&noderef).unwrap()
+        );
+
+        let project = Project::from_path("data/southpole.rsp").unwrap();
+        let noderef = NodeRef::new("/20170117_SouthPole/OBJECTS/POLYLINES/Polyline001");
+        assert_eq!(
+            Node::Polyline(&project.polylines["Polyline001"]),
+            project.resolve(&noderef).unwrap()
+        );
+        let scan_position = &project.scan_positions["ScanPos009b"];
+        let noderef = NodeRef::new(
+            "/20170117_SouthPole/SCANS/ScanPos009b/POINTCLOUDS/ScanPos009 - SINGLESCANS - 170117_204129",
+        );
+        assert_eq!(
+            Node::PointCloud(
+                scan_position,
+                &scan_position.rdb2_point_clouds["ScanPos009 - SINGLESCANS - 170117_204129"],
+            ),
+            project.resolve(&noderef).unwrap()
+        );
+        let noderef = NodeRef::new("/20170117_SouthPole/TPL (GLCS)");
+        assert_eq!(
+            Node::GlcsControlPoints(&project.glcs_control_points),
+            project.resolve(&noderef).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_sample_noderefs() {
+        use std::fs::File;
+
+        fn noderefs(element: &Element, all: &mut Vec<NodeRef>) {
+            if element.attributes.contains_key("noderef") {
+                all.push(element.noderef().unwrap());
+            }
+            for child in &element.children {
+                noderefs(child, all);
+            }
+        }
+
+        for path in vec!["data/project.RiSCAN/project.rsp", "data/southpole.rsp"] {
+            let project = Project::from_path(path).unwrap();
+            let xml = Element::parse(File::open(path).unwrap()).unwrap();
+            let mut all = Vec::new();
+            noderefs(&xml, &mut all);
+            assert!(!all.is_empty());
+            for noderef in all {
+                assert!(project.resolve(&noderef).is_ok(), "{}", noderef);
+            }
+        }
+    }
+
     #[test]
     fn mount_calibrations() {
         use utils;
@@ -256,6 +1318,61 @@ mod tests {
             .unwrap();
         let matrix = utils::parse_projective3("-0.010877741999999997 -0.003724941 -0.999933898 0.18508641   0.019274697 0.999806486 -0.0039341460000000013 0.000460517   0.99975505 -0.019316217 -0.01080384 -0.092802787   0 0 0 1").unwrap();
         assert_eq!(matrix, **mount_calibration);
+        assert!(!mount_calibration.is_adjusted);
+
+        let xml = Element::parse(
+            "<mountcalib><name>M</name><matrix>1 0 0 0 0 1 0 0 0 0 1 0 0 0 0 1</matrix></mountcalib>"
+                .as_bytes(),
+        ).unwrap();
+        assert!(!MountCalibration::from_element(&xml).unwrap().is_adjusted);
+    }
+
+    #[test]
+    fn tilt_mount_calibrations() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        assert!(project.tilt_mount_calibrations.is_empty());
+
+        let xml = Element::parse(
+            r#"<project><calibrations><tiltmounts name="TILTMOUNTS" kind="TILTMOUNTS">
+                <tiltmount name="Tilt 90" kind="tiltmount" states="deleted"/>
+            </tiltmounts></calibrations></project>"#
+                .as_bytes(),
+        ).unwrap();
+        let tilt_mount_calibrations =
+            super::object_records(xml.child("calibrations/tiltmounts").ok());
+        let tilt_mount_calibration = tilt_mount_calibrations.get("Tilt 90").unwrap();
+        assert_eq!("tiltmount", tilt_mount_calibration.kind);
+        assert!(tilt_mount_calibration.state.is_deleted());
+    }
+
+    #[test]
+    fn scan_configurations() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        assert!(project.scan_configurations.is_empty());
+
+        let xml = Element::parse(
+            r#"<project><collections><scanconfs name="CONFIGS" kind="SCANCONFS">
+                <scanconf name="Panorama 40" kind="scanconf"/>
+            </scanconfs></collections></project>"#
+                .as_bytes(),
+        ).unwrap();
+        let mut project = project.clone();
+        project.scan_configurations =
+            super::object_records(xml.child("collections/scanconfs").ok());
+        assert_eq!("scanconf", project.scan_configurations["Panorama 40"].kind);
+
+        let mut scan = project.scan_positions["SP01"].scans["151120_150227"].clone();
+        scan.scan_configuration_ref = Some(NodeRef::new(
+            "/2.2.1_Test_InfraTec_VZ1000_Calibration/COLLECTIONS/CONFIGS/Panorama 40",
+        ));
+        assert_eq!(
+            "Panorama 40",
+            scan.scan_configuration(&project).unwrap().unwrap().name
+        );
+        scan.scan_configuration_ref = Some(NodeRef::new(
+            "/2.2.1_Test_InfraTec_VZ1000_Calibration/COLLECTIONS/CONFIGS/Panorama 20",
+        ));
+        assert!(scan.scan_configuration(&project).is_err());
     }
 
     #[test]
@@ -292,9 +1409,206 @@ mod tests {
     }
 
     #[test]
-    fn only_accept_version_2_camera_calibrations() {
-        Project::from_path("data/project.RiSCAN").unwrap();
-        assert!(Project::from_path("data/camera-calibration-version-0.rsp").is_err());
-        assert!(Project::from_path("data/camera-calibration-version-1.rsp").is_err());
+    fn images() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        assert_eq!(1, project.images.len());
+        let image = &project.images["Logo"];
+        assert_eq!(Projective3::identity(), image.cop);
+        assert!(!image.is_cop_frozen);
+        assert_eq!("Logo.tif", image.file);
+        assert!(image.tie_points.is_empty());
+        assert_eq!(
+            project.camera_calibrations["Infratec_VarioCAM_HD_15mm_11-16-2015_Preston"],
+            *image.camera_calibration(&project).unwrap()
+        );
+        assert!(
+            Project::from_path("data/southpole.rsp")
+                .unwrap()
+                .images
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn reflector_calibrations() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        assert_eq!(4, project.reflector_calibrations.len());
+        let disc = &project.reflector_calibrations["RIEGL Flat 5 cm"];
+        assert_eq!(State::Default, disc.state);
+        assert_eq!(16777215, disc.color);
+        assert_eq!(0., disc.constant);
+        assert_eq!(
+            ReflectorShape::Disc {
+                diameter: 0.050000000745058061,
+                width: 0.,
+            },
+            disc.shape
+        );
+        let cylinder = &project.reflector_calibrations["RIEGL Cylinder 10 cm"];
+        assert_relative_eq!(0.050000000745058061, cylinder.constant);
+        assert_eq!(
+            ReflectorShape::Cylinder {
+                diameter: 0.10000000149011613,
+                height: 0.10000000149011613,
+            },
+            cylinder.shape
+        );
+
+        let reflcalib = Element::parse(
+            "<reflcalib><name>Sphere</name><refl_color>0</refl_color><refl_const>0</refl_const>\
+             <refl_type>Sphere</refl_type></reflcalib>"
+                .as_bytes(),
+        ).unwrap();
+        let sphere = ReflectorCalibration::from_element(&reflcalib).unwrap();
+        assert_eq!(ReflectorShape::Other("Sphere".to_string()), sphere.shape);
+
+        let reflcalib = Element::parse(
+            "<reflcalib states=\"deleted\"><name>Deleted</name><refl_color>0</refl_color>\
+             <refl_const>0</refl_const><refl_type>Disc</refl_type>\
+             <v_disc_diameter>0.1</v_disc_diameter><v_disc_width>0</v_disc_width></reflcalib>"
+                .as_bytes(),
+        ).unwrap();
+        let deleted = ReflectorCalibration::from_element(&reflcalib).unwrap();
+        assert!(deleted.state.is_deleted());
+    }
+
+    #[test]
+    fn opencv_without_metadata() {
+        let camcalib = Element::parse(
+            "<camcalib_opencv><angle_extents><tan_max_horz>1</tan_max_horz>\
+             <tan_max_vert>1</tan_max_vert><tan_min_horz>-1</tan_min_horz>\
+             <tan_min_vert>-1</tan_min_vert></angle_extents><internal_opencv><cx>512</cx>\
+             <cy>384</cy><fx>1000</fx><fy>1000</fy><k1>0</k1><k2>0</k2><k3>0</k3><k4>0</k4>\
+             <p1>0</p1><p2>0</p2></internal_opencv><intrinsic_opencv><nx>1024</nx><ny>768</ny>\
+             </intrinsic_opencv><name>Bare</name><version>2</version></camcalib_opencv>"
+                .as_bytes(),
+        ).unwrap();
+        let opencv = OpenCv::from_element(&camcalib).unwrap();
+        assert_eq!(None, opencv.camera_model);
+        assert_eq!(None, opencv.camera_serial_number);
+        assert_eq!(None, opencv.lens_model);
+        assert_eq!(None, opencv.lens_serial_number);
+        assert_eq!(None, opencv.settings);
+        assert_eq!(None, opencv.dx);
+        assert_eq!(None, opencv.dy);
+        assert_eq!(None, opencv.focal_length_mm());
+    }
+
+    #[test]
+    fn geometry_objects() {
+        let project = Project::from_path("data/southpole.rsp").unwrap();
+        assert_eq!(1, project.polylines.len());
+        assert!(project.planes.is_empty());
+        assert!(project.sections.is_empty());
+
+        let xml = Element::parse(
+            "<project><geometry_objects><plane_objects>\
+             <plane_object name=\"Plane001\" kind=\"PlaneObjectX\"><unknown/></plane_object>\
+             </plane_objects></geometry_objects></project>"
+                .as_bytes(),
+        ).unwrap();
+        let planes = super::object_records(xml.child("geometry_objects/plane_objects").ok());
+        assert_eq!("PlaneObjectX", planes["Plane001"].kind);
+        assert!(polylines(&xml).unwrap().is_empty());
+    }
+
+    #[test]
+    fn missing_containers() {
+        let xml = Element::parse(
+            "<scanposition><name>SP01</name><sop><freeze>0</freeze>\
+             <matrix>1 0 0 0 0 1 0 0 0 0 1 0 0 0 0 1</matrix></sop></scanposition>"
+                .as_bytes(),
+        ).unwrap();
+        let scan_position = ScanPosition::from_element(&xml).unwrap();
+        assert!(scan_position.images.is_empty());
+        assert!(scan_position.undistorted_images.is_empty());
+        assert!(scan_position.polydata_objects.is_empty());
+        assert!(scan_position.scans.is_empty());
+        assert!(scan_position.tiepoint_scans.is_empty());
+        assert!(scan_position.tie_points.is_empty());
+
+        let xml = Element::parse("<project/>".as_bytes()).unwrap();
+        assert!(super::control_points::<Prcs>(&xml, "tpl_prcs/tp_prcs").unwrap().is_empty());
+        assert!(image_tie_points(&xml).unwrap().is_empty());
+        assert!(optional_children(&xml, "geometry_objects/images/image").is_empty());
+    }
+
+    #[test]
+    fn polydata_folder() {
+        let xml = Element::parse(
+            r#"<scanposition><polydata_objects fold="MESHES">
+                <polydata_object fold="Mesh"><name>Mesh</name><file>index.pvtp</file>
+                <color>0</color><use_for_msa>0</use_for_msa></polydata_object>
+            </polydata_objects></scanposition>"#
+                .as_bytes(),
+        ).unwrap();
+        let polydata_objects = polydata_objects(&xml, "SP01").unwrap();
+        assert_eq!("MESHES", polydata_objects["Mesh"].polydata_folder);
+        assert_eq!("Mesh", polydata_objects["Mesh"].folder);
+    }
+
+    #[test]
+    fn object_records() {
+        let xml = Element::parse(
+            r#"<tol_socs name="TOL (SOCS)" kind="TOL_SOCS">
+                <active>1</active>
+                <tieobject name="Sphere 1" kind="tieobject"/>
+            </tol_socs>"#
+                .as_bytes(),
+        ).unwrap();
+        let object_records = super::object_records(Some(&xml));
+        assert_eq!(1, object_records.len());
+        assert_eq!("tieobject", object_records["Sphere 1"].kind);
+        assert!(super::object_records(None).is_empty());
+    }
+
+    #[test]
+    fn control_points() {
+        let project = Project::from_path("data/southpole.rsp").unwrap();
+        assert_eq!(4, project.prcs_control_points.len());
+        assert_eq!(4, project.glcs_control_points.len());
+        let prcs = &project.prcs_control_points["TP1-average"];
+        assert_eq!(
+            Point::prcs(837.63199999998323, 128.61499999999069, 2841.0598),
+            prcs.position
+        );
+        assert!(prcs.is_active);
+        assert!(prcs.is_fixed);
+        assert_eq!(Some(1.), prcs.weight);
+        assert_eq!(
+            Some("RIEGL Cylinder 10 cm"),
+            prcs.reflector_calibration_ref.as_ref().map(NodeRef::name)
+        );
+        let glcs = &project.glcs_control_points["TP1-average"];
+        assert_eq!(Point::glcs(1999837.632, 2000128.615, 2841.0598), glcs.position);
+        assert!(!glcs.is_fixed);
+        assert_eq!(None, glcs.weight);
+        let transformed = prcs.position.to_glcs(project.pop);
+        assert_relative_eq!(glcs.position.deref(), transformed.deref(), epsilon = 1e-6);
+
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        assert!(project.prcs_control_points.is_empty());
+        assert!(project.glcs_control_points.is_empty());
+        assert!(project.tie_objects.is_empty());
+    }
+
+    #[test]
+    fn camera_calibration_versions() {
+        for &(path, version) in &[
+            ("data/project.RiSCAN", 2),
+            ("data/camera-calibration-version-0.rsp", 0),
+            ("data/camera-calibration-version-1.rsp", 1),
+        ]
+        {
+            let project = Project::from_path(path).unwrap();
+            let camera_calibration = project.camera_calibrations.values().next().unwrap();
+            assert_eq!(version, camera_calibration.as_opencv().unwrap().version);
+        }
+
+        let camcalib = Element::parse(
+            "<camcalib_opencv><name>Future</name><version>3</version></camcalib_opencv>"
+                .as_bytes(),
+        ).unwrap();
+        assert!(OpenCv::from_element(&camcalib).is_err());
     }
 }
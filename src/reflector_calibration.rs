@@ -0,0 +1,42 @@
+use State;
+
+/// A reflector calibration.
+///
+/// Reflector calibrations describe the physical targets that are used as tie points.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ReflectorCalibration {
+    /// The name of the reflector calibration.
+    pub name: String,
+    /// The state of the calibration.
+    pub state: State,
+    /// The display color of the reflector, as a packed BGR integer.
+    pub color: u32,
+    /// The reflector constant, i.e. the offset from the measured surface to the target center.
+    pub constant: f64,
+    /// The shape of the reflector.
+    pub shape: ReflectorShape,
+}
+
+/// The shape of a reflector.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub enum ReflectorShape {
+    /// A flat disc.
+    Disc {
+        /// The diameter of the disc.
+        diameter: f64,
+        /// The width of the disc.
+        width: f64,
+    },
+    /// A cylinder.
+    Cylinder {
+        /// The diameter of the cylinder.
+        diameter: f64,
+        /// The height of the cylinder.
+        height: f64,
+    },
+    /// A reflector of a type that we can't read, e.g. a sphere.
+    ///
+    /// Only discs and cylinders have been seen in the wild, so other types keep their
+    /// `refl_type`.
+    Other(String),
+}
@@ -0,0 +1,231 @@
+use {NodeRef, Project};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// How the scan positions of a project were registered to each other.
+///
+/// The nodes of the graph are scan positions. Each edge records that a scan position was
+/// registered against another scan position, or against the project's control points, along with
+/// the objects that tie them together.
+///
+/// Pose estimations are not part of the graph. None of our sample projects have any, so we only
+/// know their name, kind, and state, and not which scan positions they relate.
+///
+/// # Examples
+///
+/// ```
+/// use riscan_pro::Project;
+/// let project = Project::from_path("data/southpole.rsp").unwrap();
+/// let registration_graph = project.registration_graph();
+/// assert_eq!(project.scan_positions.len(), registration_graph.scan_position_names.len());
+/// ```
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct RegistrationGraph {
+    /// The names of the scan positions, i.e. the nodes of the graph.
+    pub scan_position_names: Vec<String>,
+    /// The registration links between scan positions, i.e. the edges of the graph.
+    pub edges: Vec<RegistrationEdge>,
+}
+
+/// A link between a scan position and the scan position, or control points, that it was
+/// registered against.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct RegistrationEdge {
+    /// The name of the scan position that was registered.
+    pub from: String,
+    /// The name of the scan position that it was registered against.
+    ///
+    /// This is `None` if the scan position was registered against the project's control points.
+    pub to: Option<String>,
+    /// The method of registration.
+    pub method: RegistrationMethod,
+    /// The objects that tie the scan positions together, e.g. the linked tie points.
+    pub objects: Vec<NodeRef>,
+}
+
+/// The method used to register a scan position.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RegistrationMethod {
+    /// The scan position's tie points are linked to another scan position's tie points.
+    TiePoints,
+    /// The scan position's tie points are linked to the project's control points.
+    ControlPoints,
+}
+
+impl RegistrationGraph {
+    /// Creates the registration graph for a project.
+    ///
+    /// Edges are built from the `linked_ref` noderef of each scan position's tie points, which
+    /// points at the tie point or control point it was registered against. The `referrer_ref`
+    /// noderefs point back the other way and don't add edges. Edges are sorted by scan position,
+    /// target, and method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{Project, RegistrationGraph};
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let registration_graph = RegistrationGraph::new(&project);
+    /// assert!(registration_graph.edges.is_empty());
+    /// ```
+    pub fn new(project: &Project) -> RegistrationGraph {
+        let mut edges = BTreeMap::new();
+        for scan_position in project.scan_positions.values() {
+            let from = scan_position.name.as_str();
+            let linked = scan_position.tie_points.values().filter_map(|tie_point| {
+                tie_point.linked.as_ref()
+            });
+            for noderef in linked {
+                let target = match noderef.segments().as_slice() {
+                    [_, "SCANS", to, "TPL (SOCS)", _] if *to != from => {
+                        Some((Some(to.to_string()), RegistrationMethod::TiePoints))
+                    }
+                    [_, "TPL (PRCS)", _] | [_, "TPL (GLCS)", _] => {
+                        Some((None, RegistrationMethod::ControlPoints))
+                    }
+                    _ => None,
+                };
+                if let Some((to, method)) = target {
+                    edges
+                        .entry((from.to_string(), to, method))
+                        .or_insert_with(BTreeSet::new)
+                        .insert(noderef.clone());
+                }
+            }
+        }
+        RegistrationGraph {
+            scan_position_names: project.scan_positions.keys().cloned().collect(),
+            edges: edges
+                .into_iter()
+                .map(|((scan_position_name, target, registration_method), objects)| {
+                    RegistrationEdge {
+                        from: scan_position_name,
+                        to: target,
+                        method: registration_method,
+                        objects: objects.into_iter().collect(),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the names of the scan positions that are registered with a scan position.
+    ///
+    /// Edges are followed in both directions. Registrations against the project's control points
+    /// are not edges between scan positions, so they do not add any neighbors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/southpole.rsp").unwrap();
+    /// let registration_graph = project.registration_graph();
+    /// assert!(registration_graph.neighbors("ScanPos022").is_empty());
+    /// ```
+    pub fn neighbors(&self, scan_position_name: &str) -> Vec<&str> {
+        let neighbors: BTreeSet<_> = self.edges
+            .iter()
+            .filter_map(|edge| if edge.from == scan_position_name {
+                edge.to.as_deref()
+            } else if edge.to.as_deref() == Some(scan_position_name) {
+                Some(edge.from.as_str())
+            } else {
+                None
+            })
+            .collect();
+        neighbors.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registration_graph() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let registration_graph = project.registration_graph();
+        assert_eq!(vec!["SP01", "SP02"], registration_graph.scan_position_names);
+        assert!(registration_graph.edges.is_empty());
+        assert!(registration_graph.neighbors("SP01").is_empty());
+    }
+
+    #[test]
+    fn linked_tie_points() {
+        let mut project = Project::from_path("data/project.RiSCAN").unwrap();
+        let tie_point = project
+            .scan_positions
+            .get_mut("SP02")
+            .unwrap()
+            .tie_points
+            .values_mut()
+            .next()
+            .unwrap();
+        let noderef = NodeRef::new("/project/SCANS/SP01/TPL (SOCS)/tp001");
+        tie_point.referrers.push(noderef.clone());
+        assert!(project.registration_graph().edges.is_empty());
+
+        let tie_point = project
+            .scan_positions
+            .get_mut("SP02")
+            .unwrap()
+            .tie_points
+            .values_mut()
+            .next()
+            .unwrap();
+        tie_point.linked = Some(noderef.clone());
+        let registration_graph = project.registration_graph();
+        assert_eq!(1, registration_graph.edges.len());
+        let edge = &registration_graph.edges[0];
+        assert_eq!("SP02", edge.from);
+        assert_eq!(Some("SP01"), edge.to.as_deref());
+        assert_eq!(RegistrationMethod::TiePoints, edge.method);
+        assert_eq!(vec![noderef], edge.objects);
+    }
+
+    #[test]
+    fn neighbors() {
+        let registration_graph = RegistrationGraph {
+            scan_position_names: vec!["SP01".to_string(), "SP02".to_string(), "SP03".to_string()],
+            edges: vec![
+                RegistrationEdge {
+                    from: "SP02".to_string(),
+                    to: Some("SP01".to_string()),
+                    method: RegistrationMethod::TiePoints,
+                    objects: Vec::new(),
+                },
+                RegistrationEdge {
+                    from: "SP03".to_string(),
+                    to: None,
+                    method: RegistrationMethod::ControlPoints,
+                    objects: Vec::new(),
+                },
+            ],
+        };
+        assert_eq!(vec!["SP02"], registration_graph.neighbors("SP01"));
+        assert_eq!(vec!["SP01"], registration_graph.neighbors("SP02"));
+        assert!(registration_graph.neighbors("SP03").is_empty());
+    }
+
+    #[test]
+    fn control_points() {
+        let project = Project::from_path("data/southpole.rsp").unwrap();
+        let registration_graph = project.registration_graph();
+        assert_eq!(1, registration_graph.edges.len());
+        let edge = &registration_graph.edges[0];
+        assert_eq!("ScanPos022", edge.from);
+        assert_eq!(None, edge.to);
+        assert_eq!(RegistrationMethod::ControlPoints, edge.method);
+        assert_eq!(4, edge.objects.len());
+        assert!(registration_graph.neighbors("ScanPos022").is_empty());
+
+        let control_point = &project.prcs_control_points["TP1-average"];
+        assert_eq!(
+            "/20170117_SouthPole/TPL (GLCS)/TP1-average",
+            control_point.linked.as_ref().unwrap().path
+        );
+        assert_eq!(
+            vec![NodeRef::new("/20170117_SouthPole/SCANS/ScanPos022/TPL (SOCS)/tp001")],
+            control_point.referrers
+        );
+    }
+}
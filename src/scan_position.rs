@@ -1,9 +1,14 @@
 //! Scan positions and their consituant parts.
 
-use {CameraCalibration, Error, MountCalibration, Project, Result};
-use nalgebra::Projective3;
+use {BoundingBox, CameraCalibration, Error, Glcs, MountCalibration, Node, NodeRef, ObjectRecord,
+     Point, Prcs, Project, ReflectorCalibration, Result, Socs, State};
+use nalgebra::{Point3, Projective3, Vector3};
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The wavelength of the lasers in RIEGL's VZ-series scanners, in micrometers.
+const WAVELENGTH: f64 = 1.55;
 
 /// A scan position
 #[derive(Clone, Debug, Serialize, PartialEq)]
@@ -12,12 +17,72 @@ pub struct ScanPosition {
     pub name: String,
     /// The scan position images.
     pub images: BTreeMap<String, Image>,
+    /// The undistorted scan position images, by name.
+    pub undistorted_images: BTreeMap<String, UndistortedImage>,
     /// The scanner's own position.
     pub sop: Projective3<f64>,
-    /// The scans taken at this position.
+    /// The scans taken at this position, including deleted scans.
     pub scans: BTreeMap<String, Scan>,
+    /// The fine scans of tie point reflectors taken at this position, by name.
+    pub tiepoint_scans: BTreeMap<String, Scan>,
     /// The scan position SOP matrix is frozen.
     pub is_frozen: bool,
+    /// The position of the tilt mount the scanner was on, if any.
+    ///
+    /// RiSCAN Pro writes `-1` for scan positions without a tilt mount. The tilt is not yet applied
+    /// to the SOP.
+    pub tilt_mount_position: Option<i32>,
+    /// The onboard GNSS position of the scanner, if one was recorded and is enabled.
+    ///
+    /// RiSCAN Pro writes a zero position for scan positions without a GNSS fix, and marks them
+    /// as disabled, so disabled positions are `None`.
+    pub gnss_position: Option<GnssPosition>,
+    /// The atmospheric conditions used for the range correction, if recorded.
+    pub atmosphere: Option<Atmosphere>,
+    /// The tie points in the scanner's own coordinate system, by name.
+    pub tie_points: BTreeMap<String, TiePoint>,
+    /// The tie objects in the scanner's own coordinate system, by name.
+    pub tie_objects: BTreeMap<String, ObjectRecord>,
+    /// The polydata objects derived from this scan position's data, by name.
+    pub polydata_objects: BTreeMap<String, PolydataObject>,
+    /// The scan sequences taken at this position, by name.
+    pub scan_sequences: BTreeMap<String, DataFile>,
+    /// The profile scans taken at this position, by name.
+    pub profile_scans: BTreeMap<String, DataFile>,
+    /// The RDB2 point clouds created from this position's scans, by name.
+    pub rdb2_point_clouds: BTreeMap<String, DataFile>,
+    /// The pose estimations used to register this position, by name.
+    pub pose_estimations: BTreeMap<String, ObjectRecord>,
+}
+
+/// The position of a scan position as measured by the scanner's onboard GNSS receiver.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq)]
+pub struct GnssPosition {
+    /// The position, in earth-centered earth-fixed (ECEF) coordinates.
+    pub position: Point3<f64>,
+    /// The accuracy of the position, in meters.
+    pub accuracy: f64,
+    /// RiSCAN Pro's code for the coordinate system of the position.
+    pub coordinate_system: u32,
+}
+
+/// The atmospheric conditions at a scan position, used for the PPM range correction.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct Atmosphere {
+    /// The air pressure, in hPa (mbar).
+    pub air_pressure: f64,
+    /// The air temperature, in degrees Celsius.
+    pub air_temperature: f64,
+    /// The partial pressure of water vapor, in hPa (mbar).
+    pub moisture_pressure: f64,
+    /// The instrument the correction is calculated for, e.g. `VZ-1000`, if recorded.
+    pub instrument: Option<String>,
+    /// A scale correction for measured ranges, in parts per million, if recorded.
+    ///
+    /// This is applied on top of the atmospheric correction, which is calculated from the air
+    /// pressure, temperature, and moisture. It does not include the atmospheric correction
+    /// itself, so it is zero for most scan positions.
+    pub total_geometric_correction: Option<f64>,
 }
 
 /// A scan.
@@ -27,10 +92,85 @@ pub struct Scan {
     pub name: String,
     /// The file name of the scan.
     pub file: String,
+    /// The state of the scan.
+    pub state: State,
     /// The number of measurements in the phi direction.
     pub phi_count: usize,
     /// The number of measurements in the theta direction.
     pub theta_count: usize,
+    /// The starting phi angle, in degrees.
+    pub phi_start: f64,
+    /// The angular increment in the phi direction, in degrees.
+    pub phi_delta: f64,
+    /// The starting theta angle, in degrees.
+    pub theta_start: f64,
+    /// The angular increment in the theta direction, in degrees.
+    pub theta_delta: f64,
+    /// The instrument model, e.g. `VZ-1000`, if recorded.
+    pub instrument: Option<String>,
+    /// The instrument serial number, if recorded.
+    pub serial_number: Option<String>,
+    /// The measurement program, e.g. `450m (300kHz)`, if recorded.
+    pub measurement_program: Option<String>,
+    /// The laser clock, if recorded.
+    pub laser_clock: Option<f64>,
+    /// The beam focus, if recorded.
+    pub beam_focus: Option<f64>,
+    /// The multiple-time-around settings for this scan.
+    pub mta: MtaSettings,
+    /// The number of frames in the scan, if recorded.
+    pub frame_count: Option<usize>,
+    /// The time it took to acquire the scan, if recorded.
+    pub time: Option<Duration>,
+    /// Information about the extents of the scan's data, if it has been calculated.
+    pub geometry_info: Option<GeometryInfo>,
+    /// The reference to the scan configuration used to acquire this scan, if there is one.
+    pub scan_configuration_ref: Option<NodeRef>,
+}
+
+/// Information about the extents of a scan's data.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq)]
+pub struct GeometryInfo {
+    /// The minimum cartesian coordinates.
+    pub min_cart: Point<Socs>,
+    /// The maximum cartesian coordinates.
+    pub max_cart: Point<Socs>,
+    /// The minimum polar coordinates, as range, theta, and phi (in radians).
+    pub min_polar: Vector3<f64>,
+    /// The maximum polar coordinates, as range, theta, and phi (in radians).
+    pub max_polar: Vector3<f64>,
+    /// The minimum intensity.
+    pub intensity_min: f64,
+    /// The maximum intensity.
+    pub intensity_max: f64,
+    /// The minimum reflectance, in dB, if recorded.
+    pub reflectance_min: Option<f64>,
+    /// The maximum reflectance, in dB, if recorded.
+    pub reflectance_max: Option<f64>,
+    /// The cartesian and polar extents are valid.
+    pub is_cart_valid: bool,
+    /// The intensity range is valid.
+    pub is_intensity_valid: bool,
+    /// The reflectance range is valid, if recorded.
+    pub is_reflectance_valid: Option<bool>,
+}
+
+/// Multiple-time-around (MTA) settings.
+///
+/// Settings that are `None` are not set for this scan, and the project's settings are used
+/// instead.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq)]
+pub struct MtaSettings {
+    /// Automatic MTA zone calculation is enabled.
+    pub autocalc_enabled: Option<bool>,
+    /// The estimated maximum scan range, used for automatic calculation.
+    pub estimated_max_range: Option<f64>,
+    /// The manually-selected MTA zone.
+    pub fixed_zone: Option<u32>,
+    /// The minimum range of the manual MTA settings.
+    pub range_min: Option<f64>,
+    /// The maximum range of the manual MTA settings.
+    pub range_max: Option<f64>,
 }
 
 /// A scan position image.
@@ -38,12 +178,151 @@ pub struct Scan {
 pub struct Image {
     /// The name of the image.
     pub name: String,
+    /// The state of the image.
+    pub state: State,
+    /// The name of the scan position that holds this image.
+    pub scan_position_name: String,
     /// The camera's own position when taking the image.
     pub cop: Projective3<f64>,
-    /// The name of the image's camera calibration.
-    pub camera_calibration_name: String,
-    /// The name of the image's mount calibration.
-    pub mount_calibration_name: String,
+    /// Is the camera's own position frozen?
+    pub is_cop_frozen: bool,
+    /// The file name of the image, inside of the scan position's image directory, if recorded.
+    pub file: Option<String>,
+    /// The horizontal angle of the scanner head when the image was taken, in degrees, if
+    /// recorded.
+    pub phi: Option<f64>,
+    /// The vertical angle of the scanner head when the image was taken, in degrees, if recorded.
+    pub theta: Option<f64>,
+    /// The raw lambda angle recorded with the image, if any.
+    pub raw_lambda: Option<f64>,
+    /// The raw phi angle recorded with the image, if any.
+    pub raw_phi: Option<f64>,
+    /// The reference to the image's camera calibration.
+    pub camera_calibration_ref: NodeRef,
+    /// The reference to the image's mount calibration.
+    pub mount_calibration_ref: NodeRef,
+    /// The image tie points, by name.
+    pub tie_points: BTreeMap<String, ImageTiePoint>,
+}
+
+/// A polydata object, e.g. a filtered point cloud or a mesh created from a scan.
+///
+/// The data are stored in a VTK `.pvtp` file in the scan position's polydata directory.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct PolydataObject {
+    /// The name of the polydata object.
+    pub name: String,
+    /// The state of the polydata object.
+    pub state: State,
+    /// The name of the scan position that holds this polydata object.
+    pub scan_position_name: String,
+    /// The scan position's polydata directory, usually `POLYDATA`.
+    pub polydata_folder: String,
+    /// The directory holding the data, inside of the scan position's polydata directory.
+    pub folder: String,
+    /// The file name of the data, usually `index.pvtp`.
+    pub file: String,
+    /// The display color.
+    pub color: u32,
+    /// The data are used for multi station adjustment.
+    pub is_used_for_msa: bool,
+    /// The extents of the data, if recorded.
+    pub geometry_info: Option<GeometryInfo>,
+    /// The processing parameters, e.g. `redfact_deci` or `smooth`, by name.
+    ///
+    /// Values are kept as the raw text from the project file.
+    pub parameters: BTreeMap<String, String>,
+}
+
+/// A scan position object whose data are stored in a single file, e.g. an RDB2 point cloud.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct DataFile {
+    /// The name of the object.
+    pub name: String,
+    /// The state of the object.
+    pub state: State,
+    /// The name of the scan position that holds this object.
+    pub scan_position_name: String,
+    /// The directory holding the file, inside of the scan position's directory.
+    pub folder: String,
+    /// The file name of the data.
+    pub file: String,
+}
+
+/// An undistorted scan position image.
+///
+/// Undistorted images are created by RiSCAN Pro from a source image, removing the lens
+/// distortion. They should be used with a zero-distortion version of the source image's camera
+/// calibration.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct UndistortedImage {
+    /// The name of the image.
+    pub name: String,
+    /// The state of the image.
+    pub state: State,
+    /// The name of the scan position that holds the image.
+    pub scan_position_name: String,
+    /// The camera's own position when taking the image.
+    pub cop: Projective3<f64>,
+    /// The file name of the image, inside of the scan position's undistorted image directory.
+    pub file: String,
+    /// The reference to the image's mount calibration.
+    pub mount_calibration_ref: NodeRef,
+    /// The reference to the image from which this image was created.
+    pub source_image_ref: NodeRef,
+    /// The image tie points, by name.
+    pub tie_points: BTreeMap<String, ImageTiePoint>,
+}
+
+/// A tie point in an image, in pixel coordinates.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ImageTiePoint {
+    /// The name of the tie point.
+    ///
+    /// Image tie points are paired with scanner tie points by name.
+    pub name: String,
+    /// The horizontal pixel coordinate.
+    pub u: f64,
+    /// The vertical pixel coordinate.
+    pub v: f64,
+    /// The size of the tie point, in pixels.
+    pub size: f64,
+    /// The tie point is used for calibration.
+    pub is_active: bool,
+    /// The reference to the tie point's reflector calibration, if there is one.
+    pub reflector_calibration_ref: Option<NodeRef>,
+    /// The tie point that this tie point is linked to, usually a scanner tie point.
+    pub linked: Option<NodeRef>,
+}
+
+/// A tie point in the scanner's own coordinate system.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct TiePoint {
+    /// The name of the tie point.
+    pub name: String,
+    /// The position of the tie point.
+    pub position: Point<Socs>,
+    /// The direction from which the tie point was observed.
+    pub direction: Vector3<f64>,
+    /// The accuracy of the tie point position.
+    pub accuracy: Vector3<f64>,
+    /// The intensity of the tie point.
+    pub intensity: f64,
+    /// The reflectivity of the tie point, in dB.
+    pub reflectivity: f64,
+    /// The number of pixels (measurements) used to find the tie point.
+    pub pixels: usize,
+    /// The tie point is used for registration.
+    pub is_active: bool,
+    /// The reference to the tie point's reflector calibration, if there is one.
+    pub reflector_calibration_ref: Option<NodeRef>,
+    /// The reference to the tie point's fine scan, if there is one.
+    pub tiepoint_scan_ref: Option<NodeRef>,
+    /// The tie point that this tie point is linked to, e.g. a control point or another scan
+    /// position's tie point.
+    pub linked: Option<NodeRef>,
+    /// The tie points that are linked to this tie point, e.g. image tie points.
+    pub referrers: Vec<NodeRef>,
 }
 
 impl ScanPosition {
@@ -71,6 +350,8 @@ impl ScanPosition {
 
     /// Returns a vector of all paths to rxps in the singlescan directory.
     ///
+    /// Deleted scans are not included.
+    ///
     /// # Examples
     ///
     /// ```
@@ -81,28 +362,152 @@ impl ScanPosition {
     ///     .get("SP01")
     ///     .unwrap();
     /// let paths = scan_position.singlescan_rxp_paths(&project);
-    /// assert_eq!(4, paths.len());
+    /// assert_eq!(2, paths.len());
     /// ```
     pub fn singlescan_rxp_paths(&self, project: &Project) -> Vec<PathBuf> {
-        let mut path = project
-            .path
-            .parent()
-            .expect("Project path should always have a parent")
-            .to_path_buf();
-        path.push("SCANS");
-        path.push(&self.name);
-        path.push("SINGLESCANS");
-        self.scans
+        let path = self.directory(project, "SINGLESCANS");
+        self.scans()
+            .into_iter()
+            .map(|scan| path.join(&scan.file))
+            .collect()
+    }
+
+    /// Returns a vector of all paths to rxps in the tiepoint scan directory.
+    ///
+    /// Deleted scans are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project
+    ///     .scan_positions
+    ///     .get("SP01")
+    ///     .unwrap();
+    /// let paths = scan_position.tiepoint_scan_rxp_paths(&project);
+    /// assert_eq!(20, paths.len());
+    /// ```
+    pub fn tiepoint_scan_rxp_paths(&self, project: &Project) -> Vec<PathBuf> {
+        let path = self.directory(project, "TIEPOINTSCANS");
+        undeleted_scans(&self.tiepoint_scans)
+            .into_iter()
+            .map(|scan| path.join(&scan.file))
+            .collect()
+    }
+
+    /// Returns a vector of all paths to polydata files.
+    ///
+    /// Deleted polydata objects are not included. The paths are sorted by polydata object name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_positions.get("SP01").unwrap();
+    /// let paths = scan_position.polydata_paths(&project);
+    /// assert_eq!(2, paths.len());
+    /// ```
+    pub fn polydata_paths(&self, project: &Project) -> Vec<PathBuf> {
+        self.polydata_objects
             .values()
-            .map(|scan| {
-                let mut path = path.clone();
-                path.push(&scan.file);
-                path
-            })
+            .filter(|p| !p.state.is_deleted())
+            .map(|polydata_object| polydata_object.path(project))
             .collect()
     }
 
-    /// Returns a vector of all of the images.
+    /// Returns a vector of all paths to scan sequence files.
+    ///
+    /// Deleted scan sequences are not included. The paths are sorted by scan sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_positions.get("SP01").unwrap();
+    /// let paths = scan_position.scan_sequence_paths(&project);
+    /// assert!(paths.is_empty());
+    /// ```
+    pub fn scan_sequence_paths(&self, project: &Project) -> Vec<PathBuf> {
+        data_file_paths(&self.scan_sequences, project)
+    }
+
+    /// Returns a vector of all paths to rxps in the profile scan directory.
+    ///
+    /// Deleted profile scans are not included. The paths are sorted by profile scan name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_positions.get("SP01").unwrap();
+    /// let paths = scan_position.profile_scan_rxp_paths(&project);
+    /// assert!(paths.is_empty());
+    /// ```
+    pub fn profile_scan_rxp_paths(&self, project: &Project) -> Vec<PathBuf> {
+        data_file_paths(&self.profile_scans, project)
+    }
+
+    /// Returns a vector of all paths to RDB2 point cloud files.
+    ///
+    /// Deleted point clouds are not included. The paths are sorted by point cloud name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/southpole.rsp").unwrap();
+    /// let scan_position = project.scan_positions.get("ScanPos009b").unwrap();
+    /// let paths = scan_position.rdb2_point_cloud_paths(&project);
+    /// assert_eq!(2, paths.len());
+    /// ```
+    pub fn rdb2_point_cloud_paths(&self, project: &Project) -> Vec<PathBuf> {
+        data_file_paths(&self.rdb2_point_clouds, project)
+    }
+
+    /// Returns a vector of all of the scans that have not been deleted.
+    ///
+    /// The vector is sorted by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = &project.scan_positions["SP01"];
+    /// let scans = scan_position.scans();
+    /// assert_eq!(2, scans.len());
+    /// ```
+    pub fn scans(&self) -> Vec<&Scan> {
+        undeleted_scans(&self.scans)
+    }
+
+    /// Returns a vector of all of the scans that have been deleted.
+    ///
+    /// The vector is sorted by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = &project.scan_positions["SP01"];
+    /// let scans = scan_position.deleted_scans();
+    /// assert_eq!(2, scans.len());
+    /// ```
+    pub fn deleted_scans(&self) -> Vec<&Scan> {
+        let mut scans: Vec<_> = self.scans
+            .values()
+            .filter(|s| s.state.is_deleted())
+            .collect();
+        scans.sort_by_key(|s| &s.name);
+        scans
+    }
+
+    /// Returns a vector of all of the images that have not been deleted.
     ///
     /// The vector is sorted by name.
     ///
@@ -116,10 +521,199 @@ impl ScanPosition {
     /// assert_eq!(6, images.len());
     /// ```
     pub fn images(&self) -> Vec<&Image> {
-        let mut images: Vec<_> = self.images.values().collect();
+        let mut images: Vec<_> = self.images
+            .values()
+            .filter(|i| !i.state.is_deleted())
+            .collect();
         images.sort_by_key(|i| &i.name);
         images
     }
+
+    /// Returns the offset from the registered scanner origin to the GNSS position.
+    ///
+    /// The scanner origin is transformed to the global coordinate system using the SOP and the
+    /// provided POP, so this only makes sense if the project's global coordinate system is ECEF.
+    /// Returns `None` if there is no GNSS position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_positions.get("SP01").unwrap();
+    /// let offset = scan_position.gnss_offset(project.pop).unwrap();
+    /// assert!(offset.norm() < 1e-6);
+    /// ```
+    pub fn gnss_offset(&self, pop: Projective3<f64>) -> Option<Vector3<f64>> {
+        use std::ops::Deref;
+        self.gnss_position.map(|gnss_position| {
+            let origin = Point::socs(0., 0., 0.).to_prcs(self.sop).to_glcs(pop);
+            gnss_position.position - origin.deref()
+        })
+    }
+
+    fn directory(&self, project: &Project, name: &str) -> PathBuf {
+        directory(project, &self.name, name)
+    }
+}
+
+impl Atmosphere {
+    /// Returns the group refractivity of the air, i.e. `(n - 1) * 10^6`.
+    ///
+    /// Uses the IAG (1999) resolution formula for the group refractive index of light in moist
+    /// air, at the 1550 nm wavelength of RIEGL's VZ-series lasers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let atmosphere = project.scan_positions["SP01"].atmosphere.as_ref().unwrap();
+    /// let refractivity = atmosphere.group_refractivity();
+    /// ```
+    pub fn group_refractivity(&self) -> f64 {
+        let standard = 287.6155 + 4.88660 / WAVELENGTH.powi(2) + 0.06800 / WAVELENGTH.powi(4);
+        let temperature = 273.15 + self.air_temperature;
+        standard * 273.15 / 1013.25 * self.air_pressure / temperature -
+            11.27 * self.moisture_pressure / temperature
+    }
+
+    /// Returns an estimate of the range correction, in parts per million.
+    ///
+    /// RIEGL doesn't publish the formula that RiSCAN Pro uses, so this is the change in group
+    /// refractivity relative to the atmosphere that RiSCAN Pro writes for new scan positions
+    /// (1000 hPa, 12 °C, and 8.42 hPa moisture pressure), plus the total geometric correction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let atmosphere = project.scan_positions["SP01"].atmosphere.as_ref().unwrap();
+    /// assert!(atmosphere.estimated_ppm().abs() < 1e-6);
+    /// ```
+    pub fn estimated_ppm(&self) -> f64 {
+        let reference = Atmosphere {
+            air_pressure: 1000.,
+            air_temperature: 12.,
+            moisture_pressure: 8.42,
+            instrument: None,
+            total_geometric_correction: None,
+        };
+        reference.group_refractivity() - self.group_refractivity() +
+            self.total_geometric_correction.unwrap_or(0.)
+    }
+
+    /// Returns an estimate of the factor by which measured ranges are multiplied to correct them.
+    ///
+    /// See `estimated_ppm`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let atmosphere = project.scan_positions["SP01"].atmosphere.as_ref().unwrap();
+    /// let corrected_range = 100. * atmosphere.estimated_range_correction_factor();
+    /// ```
+    pub fn estimated_range_correction_factor(&self) -> f64 {
+        1. + self.estimated_ppm() * 1e-6
+    }
+}
+
+impl Scan {
+    /// Finds and returns the scan configuration used to acquire this scan, if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan = &project.scan_positions["SP01"].scans["151120_150227"];
+    /// assert_eq!(None, scan.scan_configuration(&project).unwrap());
+    /// ```
+    pub fn scan_configuration<'a>(
+        &self,
+        project: &'a Project,
+    ) -> Result<Option<&'a ObjectRecord>> {
+        match self.scan_configuration_ref {
+            Some(ref noderef) => match project.resolve(noderef) {
+                Ok(Node::ScanConfiguration(scan_configuration)) => Ok(Some(scan_configuration)),
+                _ => Err(Error::MissingScanConfiguration(noderef.path.clone())),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Returns this scan's bounding box in the scanner's own coordinate system.
+    ///
+    /// Returns `None` if the scan has no valid geometry information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_positions.get("SP01").unwrap();
+    /// let scan = scan_position.scans.get("151120_150227_old@20160315-120347-357").unwrap();
+    /// let bounding_box = scan.socs_bounding_box().unwrap();
+    /// assert!(scan_position.scans["151120_150227"].socs_bounding_box().is_none());
+    /// ```
+    pub fn socs_bounding_box(&self) -> Option<BoundingBox<Socs>> {
+        self.geometry_info.as_ref().and_then(|geometry_info| {
+            if geometry_info.is_cart_valid {
+                Some(BoundingBox {
+                    min: geometry_info.min_cart,
+                    max: geometry_info.max_cart,
+                })
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns this scan's bounding box in the project coordinate system.
+    ///
+    /// The box is transformed by the scan position's SOP, so it is a conservative bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_positions.get("SP01").unwrap();
+    /// let scan = scan_position.scans.get("151120_150227_old@20160315-120347-357").unwrap();
+    /// let bounding_box = scan.prcs_bounding_box(scan_position.sop).unwrap();
+    /// ```
+    pub fn prcs_bounding_box(&self, sop: Projective3<f64>) -> Option<BoundingBox<Prcs>> {
+        self.socs_bounding_box().map(
+            |bounding_box| bounding_box.to_prcs(sop),
+        )
+    }
+
+    /// Returns this scan's bounding box in the global coordinate system.
+    ///
+    /// The box is transformed by the scan position's SOP and the project's POP, so it is a
+    /// conservative bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_positions.get("SP01").unwrap();
+    /// let scan = scan_position.scans.get("151120_150227_old@20160315-120347-357").unwrap();
+    /// let bounding_box = scan.glcs_bounding_box(scan_position.sop, project.pop).unwrap();
+    /// ```
+    pub fn glcs_bounding_box(
+        &self,
+        sop: Projective3<f64>,
+        pop: Projective3<f64>,
+    ) -> Option<BoundingBox<Glcs>> {
+        self.socs_bounding_box().map(|bounding_box| {
+            bounding_box.to_prcs(sop).to_glcs(pop)
+        })
+    }
 }
 
 impl Image {
@@ -128,7 +722,7 @@ impl Image {
     /// # Examples
     ///
     /// ```
-    /// use riscan_pro::Project;
+    /// use riscan_pro::{NodeRef, Project};
     /// let project = Project::from_path("data/project.RiSCAN").unwrap();
     /// let mut image = project.scan_positions
     ///     .get("SP01")
@@ -138,16 +732,16 @@ impl Image {
     ///     .unwrap()
     ///     .clone();
     /// image.camera_calibration(&project).unwrap();
-    /// image.camera_calibration_name = "Not a camera calibration".to_string();
+    /// image.camera_calibration_ref = NodeRef::new("/Project/CALIBRATIONS/CAMERA/Not a camera");
     /// assert!(image.camera_calibration(&project).is_err());
     /// ```
     pub fn camera_calibration<'a>(&self, project: &'a Project) -> Result<&'a CameraCalibration> {
-        project
-            .camera_calibrations
-            .get(&self.camera_calibration_name)
-            .ok_or_else(|| {
-                Error::MissingCameraCalibration(self.camera_calibration_name.clone())
-            })
+        match project.resolve(&self.camera_calibration_ref) {
+            Ok(Node::CameraCalibration(camera_calibration)) => Ok(camera_calibration),
+            _ => Err(Error::MissingCameraCalibration(
+                self.camera_calibration_ref.path.clone(),
+            )),
+        }
     }
 
     /// Finds and returns this image's mount calibration.
@@ -155,7 +749,7 @@ impl Image {
     /// # Examples
     ///
     /// ```
-    /// use riscan_pro::Project;
+    /// use riscan_pro::{NodeRef, Project};
     /// let project = Project::from_path("data/project.RiSCAN").unwrap();
     /// let mut image = project.scan_positions
     ///     .get("SP01")
@@ -165,17 +759,302 @@ impl Image {
     ///     .unwrap()
     ///     .clone();
     /// image.mount_calibration(&project).unwrap();
-    /// image.mount_calibration_name = "Not a mount calibration".to_string();
+    /// image.mount_calibration_ref = NodeRef::new("/Project/CALIBRATIONS/MOUNTING/Not a mount");
     /// assert!(image.mount_calibration(&project).is_err());
     /// ```
     pub fn mount_calibration<'a>(&self, project: &'a Project) -> Result<&'a MountCalibration> {
-        project
-            .mount_calibrations
-            .get(&self.mount_calibration_name)
-            .ok_or_else(|| {
-                Error::MissingMountCalibration(self.mount_calibration_name.clone())
-            })
+        match project.resolve(&self.mount_calibration_ref) {
+            Ok(Node::MountCalibration(mount_calibration)) => Ok(mount_calibration),
+            _ => Err(Error::MissingMountCalibration(
+                self.mount_calibration_ref.path.clone(),
+            )),
+        }
+    }
+}
+
+impl Image {
+    /// Returns the path to this image's file, if it has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let image = &project.scan_positions["SP01"].images["SP01 - Image001"];
+    /// let path = image.path(&project).unwrap();
+    /// assert!(path.ends_with("SCANS/SP01/SCANPOSIMAGES/SP01 - Image001.jpg"));
+    /// ```
+    pub fn path(&self, project: &Project) -> Option<PathBuf> {
+        self.file.as_ref().map(|file| {
+            directory(project, &self.scan_position_name, "SCANPOSIMAGES").join(file)
+        })
+    }
+}
+
+impl PolydataObject {
+    /// Returns the path to this polydata object's file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/southpole.rsp").unwrap();
+    /// let polydata_object = project.scan_positions["ScanPos009b"]
+    ///     .polydata_objects
+    ///     .values()
+    ///     .next()
+    ///     .unwrap();
+    /// let path = polydata_object.path(&project);
+    /// assert!(path.ends_with(
+    ///     "SCANS/ScanPos009b/POLYDATA/ScanPos009 - SINGLESCANS - 170117_204129/index.pvtp"
+    /// ));
+    /// ```
+    pub fn path(&self, project: &Project) -> PathBuf {
+        directory(project, &self.scan_position_name, &self.polydata_folder)
+            .join(&self.folder)
+            .join(&self.file)
+    }
+}
+
+impl DataFile {
+    /// Returns the path to this object's file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/southpole.rsp").unwrap();
+    /// let point_cloud = &project.scan_positions["ScanPos009b"].rdb2_point_clouds
+    ///     ["ScanPos009 - SINGLESCANS - 170117_204129"];
+    /// let path = point_cloud.path(&project);
+    /// assert!(path.ends_with(
+    ///     "SCANS/ScanPos009b/POINTCLOUDS/ScanPos009 - SINGLESCANS - 170117_204129.rdbx"
+    /// ));
+    /// ```
+    pub fn path(&self, project: &Project) -> PathBuf {
+        directory(project, &self.scan_position_name, &self.folder).join(&self.file)
+    }
+}
+
+impl UndistortedImage {
+    /// Returns the path to this image's file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let image = &project.scan_positions["SP01"].undistorted_images["SP01 - Image001"];
+    /// let path = image.path(&project);
+    /// assert!(path.ends_with("SCANS/SP01/SCANPOSUNDISTIMAGES/SP01 - Image001.jpg"));
+    /// ```
+    pub fn path(&self, project: &Project) -> PathBuf {
+        directory(project, &self.scan_position_name, "SCANPOSUNDISTIMAGES").join(&self.file)
+    }
+
+    /// Finds and returns the image from which this image was created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_positions.get("SP01").unwrap();
+    /// let undistorted_image = scan_position.undistorted_images.get("SP01 - Image001").unwrap();
+    /// let image = undistorted_image.source_image(&project).unwrap();
+    /// assert_eq!("SP01 - Image001", image.name);
+    /// ```
+    pub fn source_image<'a>(&self, project: &'a Project) -> Result<&'a Image> {
+        match project.resolve(&self.source_image_ref) {
+            Ok(Node::Image(_, image)) => Ok(image),
+            _ => Err(Error::MissingImage(self.source_image_ref.path.clone())),
+        }
+    }
+
+    /// Returns the camera calibration for this image.
+    ///
+    /// This is the source image's camera calibration without any lens distortion, see
+    /// `CameraCalibration::undistorted`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_positions.get("SP01").unwrap();
+    /// let undistorted_image = scan_position.undistorted_images.get("SP01 - Image001").unwrap();
+    /// let camera_calibration = undistorted_image.camera_calibration(&project).unwrap();
+    /// assert!(camera_calibration.as_pinhole().is_some());
+    /// ```
+    pub fn camera_calibration(&self, project: &Project) -> Result<CameraCalibration> {
+        self.source_image(project)
+            .and_then(|image| image.camera_calibration(project))
+            .map(|camera_calibration| camera_calibration.undistorted())
+    }
+
+    /// Finds and returns this image's mount calibration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let undistorted_image = project.scan_positions
+    ///     .get("SP01")
+    ///     .unwrap()
+    ///     .undistorted_images
+    ///     .get("SP01 - Image001")
+    ///     .unwrap();
+    /// undistorted_image.mount_calibration(&project).unwrap();
+    /// ```
+    pub fn mount_calibration<'a>(&self, project: &'a Project) -> Result<&'a MountCalibration> {
+        match project.resolve(&self.mount_calibration_ref) {
+            Ok(Node::MountCalibration(mount_calibration)) => Ok(mount_calibration),
+            _ => Err(Error::MissingMountCalibration(
+                self.mount_calibration_ref.path.clone(),
+            )),
+        }
+    }
+}
+
+impl TiePoint {
+    /// Finds and returns this tie point's reflector calibration, if it has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let tie_point = project.scan_positions
+    ///     .get("SP01")
+    ///     .unwrap()
+    ///     .tie_points
+    ///     .get("tp001")
+    ///     .unwrap();
+    /// let reflector_calibration = tie_point.reflector_calibration(&project).unwrap().unwrap();
+    /// assert_eq!("GSE Flat 10 cm", reflector_calibration.name);
+    /// ```
+    pub fn reflector_calibration<'a>(
+        &self,
+        project: &'a Project,
+    ) -> Result<Option<&'a ReflectorCalibration>> {
+        match self.reflector_calibration_ref {
+            Some(ref noderef) => match project.resolve(noderef) {
+                Ok(Node::ReflectorCalibration(reflector_calibration)) => {
+                    Ok(Some(reflector_calibration))
+                }
+                _ => Err(Error::MissingReflectorCalibration(noderef.path.clone())),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Finds and returns this tie point's fine scan, if it has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_positions.get("SP01").unwrap();
+    /// let tie_point = scan_position.tie_points.get("tp020").unwrap();
+    /// let scan = tie_point.tiepoint_scan(&project).unwrap().unwrap();
+    /// assert_eq!("tp020.rxp", scan.file);
+    /// ```
+    pub fn tiepoint_scan<'a>(&self, project: &'a Project) -> Result<Option<&'a Scan>> {
+        match self.tiepoint_scan_ref {
+            Some(ref noderef) => match project.resolve(noderef) {
+                Ok(Node::TiepointScan(_, scan)) => Ok(Some(scan)),
+                _ => Err(Error::MissingTiepointScan(noderef.path.clone())),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+impl ImageTiePoint {
+    /// Finds and returns the scanner's tie point with the same name as this image tie point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_positions.get("SP01").unwrap();
+    /// let image_tie_point = scan_position.images
+    ///     .get("SP01 - Image001")
+    ///     .unwrap()
+    ///     .tie_points
+    ///     .get("tp020")
+    ///     .unwrap();
+    /// let tie_point = image_tie_point.tie_point(scan_position).unwrap();
+    /// assert_eq!("tp020", tie_point.name);
+    /// ```
+    pub fn tie_point<'a>(&self, scan_position: &'a ScanPosition) -> Result<&'a TiePoint> {
+        scan_position.tie_points.get(&self.name).ok_or_else(|| {
+            Error::MissingTiePoint(self.name.clone())
+        })
     }
+
+    /// Finds and returns this image tie point's reflector calibration, if it has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let image_tie_point = project.scan_positions
+    ///     .get("SP01")
+    ///     .unwrap()
+    ///     .images
+    ///     .get("SP01 - Image001")
+    ///     .unwrap()
+    ///     .tie_points
+    ///     .get("tp020")
+    ///     .unwrap();
+    /// let reflector_calibration = image_tie_point.reflector_calibration(&project).unwrap();
+    /// assert!(reflector_calibration.is_some());
+    /// ```
+    pub fn reflector_calibration<'a>(
+        &self,
+        project: &'a Project,
+    ) -> Result<Option<&'a ReflectorCalibration>> {
+        match self.reflector_calibration_ref {
+            Some(ref noderef) => match project.resolve(noderef) {
+                Ok(Node::ReflectorCalibration(reflector_calibration)) => {
+                    Ok(Some(reflector_calibration))
+                }
+                _ => Err(Error::MissingReflectorCalibration(noderef.path.clone())),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+fn undeleted_scans(scans: &BTreeMap<String, Scan>) -> Vec<&Scan> {
+    let mut scans: Vec<_> = scans.values().filter(|s| !s.state.is_deleted()).collect();
+    scans.sort_by_key(|s| &s.name);
+    scans
+}
+
+fn data_file_paths(data_files: &BTreeMap<String, DataFile>, project: &Project) -> Vec<PathBuf> {
+    data_files
+        .values()
+        .filter(|d| !d.state.is_deleted())
+        .map(|data_file| data_file.path(project))
+        .collect()
+}
+
+fn directory(project: &Project, scan_position_name: &str, name: &str) -> PathBuf {
+    let mut path = project
+        .path
+        .parent()
+        .expect("Project path should always have a parent")
+        .to_path_buf();
+    path.push("SCANS");
+    path.push(scan_position_name);
+    path.push(name);
+    path
 }
 
 #[cfg(test)]
@@ -187,6 +1066,363 @@ mod tests {
         let project = Project::from_path("data/project.RiSCAN").unwrap();
         let scan_position = project.scan_positions.get("SP01").unwrap();
         let paths = scan_position.singlescan_rxp_paths(&project);
-        assert_eq!(4, paths.len());
+        assert_eq!(2, paths.len());
+        assert!(paths.iter().all(|path| {
+            path.file_name().unwrap() == "151120_150227.rxp" ||
+                path.file_name().unwrap() == "151120_150404.rxp"
+        }));
+    }
+
+    #[test]
+    fn scan_position_tiepoint_scan_rxp_paths() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let scan_position = project.scan_positions.get("SP01").unwrap();
+        let paths = scan_position.tiepoint_scan_rxp_paths(&project);
+        assert_eq!(20, paths.len());
+        assert!(paths[0].ends_with("SCANS/SP01/TIEPOINTSCANS/tp001.rxp"));
+    }
+
+    #[test]
+    fn tie_point_tiepoint_scan() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let scan_position = project.scan_positions.get("SP01").unwrap();
+        let mut tie_point = scan_position.tie_points["tp001"].clone();
+        assert_eq!(
+            scan_position.tiepoint_scans["tp001"],
+            *tie_point.tiepoint_scan(&project).unwrap().unwrap()
+        );
+        tie_point.tiepoint_scan_ref = None;
+        assert_eq!(None, tie_point.tiepoint_scan(&project).unwrap());
+        tie_point.tiepoint_scan_ref = Some(NodeRef::new("/Project/SCANS/SP01/TIEPOINTSCANS/tp999"));
+        assert!(tie_point.tiepoint_scan(&project).is_err());
+        tie_point.tiepoint_scan_ref = Some(NodeRef::new("/Project/SCANS/SP01/SINGLESCANS/tp001"));
+        assert!(tie_point.tiepoint_scan(&project).is_err());
+    }
+
+    #[test]
+    fn scan_position_deleted_scans() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let scan_position = project.scan_positions.get("SP01").unwrap();
+        assert_eq!(4, scan_position.scans.len());
+        let deleted_scans = scan_position.deleted_scans();
+        assert_eq!(2, deleted_scans.len());
+        assert_eq!(
+            State::Deleted { date: Some("2016-03-15 12:03:47+357".to_string()) },
+            deleted_scans[0].state
+        );
+        assert_eq!(State::Default, scan_position.scans()[0].state);
+    }
+
+    #[test]
+    fn scan_position_gnss_position() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let scan_position = project.scan_positions.get("SP01").unwrap();
+        let gnss_position = scan_position.gnss_position.unwrap();
+        assert_eq!(
+            Point3::new(-515634.85466817376, -5519672.4159478918, 3143442.4306359168),
+            gnss_position.position
+        );
+        assert_relative_eq!(2.3598422620363482, gnss_position.accuracy);
+        assert_eq!(2, gnss_position.coordinate_system);
+        assert_relative_eq!(
+            0.,
+            scan_position.gnss_offset(project.pop).unwrap().norm(),
+            epsilon = 1e-6
+        );
+
+        let project = Project::from_path("data/southpole.rsp").unwrap();
+        for scan_position in project.scan_positions.values() {
+            assert_eq!(None, scan_position.gnss_position);
+            assert_eq!(None, scan_position.gnss_offset(project.pop));
+        }
+    }
+
+    #[test]
+    fn scan_position_atmosphere() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let atmosphere = project.scan_positions["SP01"].atmosphere.as_ref().unwrap();
+        assert_eq!(1000., atmosphere.air_pressure);
+        assert_eq!(12., atmosphere.air_temperature);
+        assert_relative_eq!(8.4199999999987738, atmosphere.moisture_pressure);
+        assert_eq!(Some("VZ-1000"), atmosphere.instrument.as_deref());
+        assert_eq!(Some(0.), atmosphere.total_geometric_correction);
+        assert_relative_eq!(0., atmosphere.estimated_ppm(), epsilon = 1e-9);
+        assert_relative_eq!(1., atmosphere.estimated_range_correction_factor(), epsilon = 1e-15);
+
+        // The usual rule of thumb for near infrared EDM is about +1 ppm per °C and -0.3 ppm per
+        // hPa.
+        let mut warmer = atmosphere.clone();
+        warmer.air_temperature += 1.;
+        assert_relative_eq!(1., warmer.estimated_ppm(), epsilon = 0.05);
+        let mut higher = atmosphere.clone();
+        higher.air_pressure += 1.;
+        assert_relative_eq!(-0.28, higher.estimated_ppm(), epsilon = 0.02);
+        let mut corrected = atmosphere.clone();
+        corrected.total_geometric_correction = Some(10.);
+        assert_relative_eq!(10., corrected.estimated_ppm(), epsilon = 1e-9);
+
+        let project = Project::from_path("data/southpole.rsp").unwrap();
+        let atmosphere = project.scan_positions["ScanPos001"]
+            .atmosphere
+            .clone()
+            .unwrap();
+        assert_eq!(700., atmosphere.air_pressure);
+        assert_eq!(-29., atmosphere.air_temperature);
+        assert!(atmosphere.estimated_ppm() > 0.);
+        let atmosphere = project.scan_positions["ScanPos009b"]
+            .atmosphere
+            .clone()
+            .unwrap();
+        assert_eq!(Some("UNKNOWN"), atmosphere.instrument.as_deref());
+        let mut vz = atmosphere.clone();
+        vz.instrument = Some("VZ-1000".to_string());
+        assert_eq!(vz.estimated_ppm(), atmosphere.estimated_ppm());
+    }
+
+    #[test]
+    fn tilt_mount_position() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let scan_position = project.scan_positions.get("SP01").unwrap();
+        assert_eq!(None, scan_position.tilt_mount_position);
+    }
+
+    #[test]
+    fn polydata_objects() {
+        let project = Project::from_path("data/southpole.rsp").unwrap();
+        let scan_position = project.scan_positions.get("ScanPos009b").unwrap();
+        assert_eq!(1, scan_position.polydata_objects.len());
+        let polydata_object = scan_position
+            .polydata_objects
+            .get("ScanPos009 - SINGLESCANS - 170117_204129")
+            .unwrap();
+        assert_eq!("ScanPos009b", polydata_object.scan_position_name);
+        assert_eq!("POLYDATA", polydata_object.polydata_folder);
+        assert_eq!("ScanPos009 - SINGLESCANS - 170117_204129", polydata_object.folder);
+        assert_eq!("index.pvtp", polydata_object.file);
+        assert_eq!(16, polydata_object.color);
+        assert!(polydata_object.is_used_for_msa);
+        let geometry_info = polydata_object.geometry_info.unwrap();
+        assert_eq!(0.088609136641025549, geometry_info.intensity_max);
+        assert_eq!(None, geometry_info.reflectance_max);
+        assert_eq!(None, geometry_info.is_reflectance_valid);
+        assert_eq!("1", polydata_object.parameters["verbose_level"]);
+        assert_eq!("0", polydata_object.parameters["redfact_deci"]);
+        assert_eq!("", polydata_object.parameters["text"]);
+        assert!(!polydata_object.parameters.contains_key("name"));
+        assert!(!polydata_object.parameters.contains_key("file"));
+        assert!(!polydata_object.parameters.contains_key("geometryinfo"));
+        assert_eq!(
+            vec![polydata_object.path(&project)],
+            scan_position.polydata_paths(&project)
+        );
+
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let polydata_object = project.scan_positions["SP01"]
+            .polydata_objects
+            .get("151120_150227_0.05m_100pts_0.128_4.096_pp")
+            .unwrap();
+        assert_eq!(None, polydata_object.geometry_info);
+    }
+
+    #[test]
+    fn scan() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let scan_position = project.scan_positions.get("SP01").unwrap();
+        let scan = &scan_position.scans["151120_150404@20160315-120349-059"];
+        assert_eq!("151120_150404@20160315-120349-059.rxp", scan.file);
+        assert_eq!(5081, scan.phi_count);
+        assert_eq!(2513, scan.theta_count);
+        assert_relative_eq!(113.75800323486328, scan.phi_start);
+        assert_relative_eq!(0.02500000037252903, scan.phi_delta);
+        assert_relative_eq!(50.237998962402336, scan.theta_start);
+        assert_relative_eq!(0.02500000037252903, scan.theta_delta);
+        assert_eq!(Some("VZ-1000"), scan.instrument.as_deref());
+        assert_eq!(Some("S9998518"), scan.serial_number.as_deref());
+        assert_eq!(Some("450m (300kHz)"), scan.measurement_program.as_deref());
+        assert_eq!(Some(0.), scan.laser_clock);
+        assert_eq!(Some(0.), scan.beam_focus);
+        assert_eq!(
+            MtaSettings {
+                autocalc_enabled: None,
+                estimated_max_range: None,
+                fixed_zone: None,
+                range_min: Some(0.),
+                range_max: Some(3000.),
+            },
+            scan.mta
+        );
+        assert_eq!(Some(1), scan.frame_count);
+        assert_eq!(Some(Duration::from_secs(168)), scan.time);
+        assert_eq!(None, scan_position.scans["151120_150404"].time);
+    }
+
+    #[test]
+    fn scan_geometry_info() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let scan_position = project.scan_positions.get("SP01").unwrap();
+        let scan = &scan_position.scans["151120_150227_old@20160315-120347-357"];
+        let geometry_info = scan.geometry_info.as_ref().unwrap();
+        assert_eq!(
+            Point::socs(-321.39749145507814, -425.26422119140627, -38.77893829345703),
+            geometry_info.min_cart
+        );
+        assert_eq!(
+            Vector3::new(426.62612915039066, 2.2712407112121581, 6.2901668548583987),
+            geometry_info.max_polar
+        );
+        assert_relative_eq!(0.00068665598519146445, geometry_info.intensity_min);
+        assert_relative_eq!(30.010000228881837, geometry_info.reflectance_max.unwrap());
+        assert!(geometry_info.is_cart_valid);
+        assert!(geometry_info.is_intensity_valid);
+        assert_eq!(Some(true), geometry_info.is_reflectance_valid);
+        assert!(scan_position.scans["151120_150227"].geometry_info.is_none());
+
+        let socs = scan.socs_bounding_box().unwrap();
+        let glcs = scan.glcs_bounding_box(scan_position.sop, project.pop).unwrap();
+        for corner in socs.corners() {
+            assert!(glcs.contains(&corner.to_prcs(scan_position.sop).to_glcs(project.pop)));
+        }
+    }
+
+    #[test]
+    fn scan_position_tie_points() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let scan_position = project.scan_positions.get("SP01").unwrap();
+        assert_eq!(20, scan_position.tie_points.len());
+        let tie_point = &scan_position.tie_points["tp001"];
+        assert_eq!(
+            Point::socs(-16.002730909121258, -7.7914503973701389, 11.430690765380859),
+            tie_point.position
+        );
+        assert_relative_eq!(0.75651846782518272, tie_point.direction.x);
+        assert_relative_eq!(0.0050668329931795597, tie_point.accuracy.x);
+        assert_relative_eq!(0.058934755623340608, tie_point.intensity);
+        assert_relative_eq!(24.134069442749024, tie_point.reflectivity);
+        assert_eq!(5597, tie_point.pixels);
+        assert!(tie_point.is_active);
+        assert_eq!(
+            Some(NodeRef::new(
+                "/2.2.1_Test_InfraTec_VZ1000_Calibration/CALIBRATIONS/REFLECTOR/GSE Flat 10 cm",
+            )),
+            tie_point.reflector_calibration_ref
+        );
+    }
+
+    #[test]
+    fn tie_point_reflector_calibration() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let mut tie_point = project.scan_positions["SP01"].tie_points["tp001"].clone();
+        assert_eq!(
+            project.reflector_calibrations["GSE Flat 10 cm"],
+            *tie_point.reflector_calibration(&project).unwrap().unwrap()
+        );
+        tie_point.reflector_calibration_ref =
+            Some(NodeRef::new("/Project/CALIBRATIONS/REFLECTOR/Not a reflector"));
+        assert!(tie_point.reflector_calibration(&project).is_err());
+        tie_point.reflector_calibration_ref = None;
+        assert_eq!(None, tie_point.reflector_calibration(&project).unwrap());
+    }
+
+    #[test]
+    fn image() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let image = &project.scan_positions["SP01"].images["SP01 - Image001"];
+        assert_eq!("SP01", image.scan_position_name);
+        assert!(!image.is_cop_frozen);
+        assert_eq!(Some("SP01 - Image001.jpg"), image.file.as_deref());
+        assert_eq!(Some(138.49700000000002), image.phi);
+        assert_eq!(Some(90.), image.theta);
+        assert_eq!(None, image.raw_lambda);
+        assert_eq!(None, image.raw_phi);
+        assert_eq!(
+            project
+                .path
+                .parent()
+                .unwrap()
+                .join("SCANS/SP01/SCANPOSIMAGES/SP01 - Image001.jpg"),
+            image.path(&project).unwrap()
+        );
+        let mut image = image.clone();
+        image.file = None;
+        assert_eq!(None, image.path(&project));
+    }
+
+    #[test]
+    fn undistorted_images() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let scan_position = project.scan_positions.get("SP01").unwrap();
+        assert_eq!(2, scan_position.undistorted_images.len());
+        let undistorted_image = &scan_position.undistorted_images["SP01 - Image001"];
+        let image = &scan_position.images["SP01 - Image001"];
+        assert_eq!(image.cop, undistorted_image.cop);
+        assert_eq!("SP01 - Image001.jpg", undistorted_image.file);
+        assert_eq!("SP01", undistorted_image.scan_position_name);
+        assert_eq!(image, undistorted_image.source_image(&project).unwrap());
+        assert_eq!(1, undistorted_image.tie_points.len());
+        assert_eq!(None, undistorted_image.tie_points["tp001"].reflector_calibration_ref);
+
+        let camera_calibration = undistorted_image.camera_calibration(&project).unwrap();
+        let camera_calibration = camera_calibration.as_pinhole().unwrap();
+        let source_camera_calibration = image
+            .camera_calibration(&project)
+            .unwrap()
+            .as_opencv()
+            .unwrap();
+        assert_eq!(source_camera_calibration.fx, camera_calibration.fx);
+        assert_eq!(source_camera_calibration.cx, camera_calibration.cx);
+        assert!(
+            project.scan_positions["SP02"]
+                .undistorted_images
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn image_tie_points() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let scan_position = project.scan_positions.get("SP01").unwrap();
+        let image = scan_position.images.get("SP01 - Image001").unwrap();
+        assert_eq!(5, image.tie_points.len());
+        let image_tie_point = &image.tie_points["tp020"];
+        assert_relative_eq!(705.56, image_tie_point.u);
+        assert_relative_eq!(727., image_tie_point.v);
+        assert_relative_eq!(0., image_tie_point.size);
+        assert!(image_tie_point.is_active);
+        assert_eq!(
+            Some("GSE Flat 10 cm"),
+            image_tie_point.reflector_calibration_ref.as_ref().map(NodeRef::name)
+        );
+        assert_eq!(
+            scan_position.tie_points["tp020"],
+            *image_tie_point.tie_point(scan_position).unwrap()
+        );
+        assert!(!scan_position.images["SP01 - Image004"].tie_points["tp006"].is_active);
+    }
+
+    #[test]
+    fn rdb2_point_clouds() {
+        let project = Project::from_path("data/southpole.rsp").unwrap();
+        let scan_position = project.scan_positions.get("ScanPos009b").unwrap();
+        assert!(scan_position.scan_sequences.is_empty());
+        assert!(scan_position.profile_scans.is_empty());
+        assert_eq!(2, scan_position.rdb2_point_clouds.len());
+        let point_cloud = scan_position
+            .rdb2_point_clouds
+            .get("ScanPos009 - SINGLESCANS - 170117_204129_1cm_octree")
+            .unwrap();
+        assert_eq!("ScanPos009b", point_cloud.scan_position_name);
+        assert_eq!("POINTCLOUDS", point_cloud.folder);
+        assert_eq!(
+            "ScanPos009 - SINGLESCANS - 170117_204129_1cm_octree.rdbx",
+            point_cloud.file
+        );
+        assert!(!point_cloud.state.is_deleted());
+        let paths = scan_position.rdb2_point_cloud_paths(&project);
+        assert_eq!(point_cloud.path(&project), paths[1]);
+        assert!(
+            project.scan_positions["ScanPos001"]
+                .rdb2_point_clouds
+                .is_empty()
+        );
     }
 }
@@ -0,0 +1,32 @@
+/// The state of a RiSCAN Pro object.
+///
+/// RiSCAN Pro doesn't remove deleted objects from the project file, but instead marks them as
+/// deleted and keeps them around as history.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub enum State {
+    /// The object is live.
+    Default,
+    /// The object has been deleted.
+    Deleted {
+        /// The date the object was deleted, as recorded by RiSCAN Pro.
+        date: Option<String>,
+    },
+}
+
+impl State {
+    /// Returns true if this object has been deleted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::State;
+    /// assert!(!State::Default.is_deleted());
+    /// assert!(State::Deleted { date: None }.is_deleted());
+    /// ```
+    pub fn is_deleted(&self) -> bool {
+        match *self {
+            State::Default => false,
+            State::Deleted { .. } => true,
+        }
+    }
+}
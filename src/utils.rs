@@ -1,9 +1,11 @@
 //! Utility functions.
 
 use Result;
-use nalgebra::Projective3;
+use chrono::NaiveDateTime;
+use nalgebra::{Projective3, Vector3};
 use std;
 use std::io::Write;
+use std::time::Duration;
 
 /// Parse a projective3 matrix from whitespace-delimited text.
 pub fn parse_projective3(s: &str) -> Result<Projective3<f64>> {
@@ -21,6 +23,82 @@ pub fn parse_projective3(s: &str) -> Result<Projective3<f64>> {
     }
 }
 
+/// Parse a three-dimensional vector from whitespace-delimited text.
+pub fn parse_vector3(s: &str) -> Result<Vector3<f64>> {
+    use Error;
+
+    let numbers = s.split_whitespace()
+        .map(|s| s.parse::<f64>().map_err(Error::from))
+        .collect::<Result<Vec<_>>>()?;
+    if numbers.len() != 3 {
+        Err(Error::ParseVector3(s.to_string()))
+    } else {
+        Ok(Vector3::from_iterator(numbers))
+    }
+}
+
+/// Parse a timestamp from RiSCAN Pro's format, e.g. `2015-11-20 08:45:54+085`.
+///
+/// The three digits after the plus sign are milliseconds, not a time zone offset.
+///
+/// # Examples
+///
+/// ```
+/// use riscan_pro::utils;
+/// let timestamp = utils::parse_timestamp("2015-11-20 08:45:54+085").unwrap();
+/// assert_eq!("2015-11-20 08:45:54.085", timestamp.to_string());
+/// ```
+pub fn parse_timestamp(s: &str) -> Result<NaiveDateTime> {
+    use Error;
+
+    NaiveDateTime::parse_from_str(s.trim(), "%Y-%m-%d %H:%M:%S+%3f")
+        .map_err(|_| Error::ParseTimestamp(s.to_string()))
+}
+
+/// Parse a duration from RiSCAN Pro's minutes and seconds format, e.g. `2' 48"`.
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    use Error;
+
+    let mut seconds = 0;
+    for token in s.split_whitespace() {
+        let (number, multiplier) = if let Some(minutes) = token.strip_suffix('\'') {
+            (minutes, 60)
+        } else if let Some(seconds) = token.strip_suffix('"') {
+            (seconds, 1)
+        } else {
+            return Err(Error::ParseDuration(s.to_string()));
+        };
+        seconds += number.parse::<u64>()? * multiplier;
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Formats a projective3 matrix as RiSCAN Pro text, i.e. row-major with rows separated by three
+/// spaces.
+///
+/// The formatted text parses back to the same matrix.
+///
+/// # Examples
+///
+/// ```
+/// use riscan_pro::Project;
+/// use riscan_pro::utils;
+/// let project = Project::from_path("data/project.RiSCAN").unwrap();
+/// let text = utils::format_projective3(&project.pop);
+/// assert_eq!(project.pop, utils::parse_projective3(&text).unwrap());
+/// ```
+pub fn format_projective3(matrix: &Projective3<f64>) -> String {
+    let mut text = String::from(" ");
+    for row in 0..4 {
+        let row = (0..4)
+            .map(|col| matrix[(row, col)].to_string())
+            .collect::<Vec<_>>();
+        text.push_str(&row.join(" "));
+        text.push_str("   ");
+    }
+    text
+}
+
 /// Writes a projective3 to a `Write`.
 pub fn write_projective3<W: Write>(mut write: W, matrix: &Projective3<f64>) -> std::io::Result<()> {
     for row in 0..4 {
@@ -50,5 +128,38 @@ mod tests {
         );
         assert!(parse_projective3("1 0 0 0 0 1 0 0 0 0 1 0 0 0 1").is_err());
         assert!(parse_projective3("1 0 0 0 0 1 0 0 0 0 1 0 0 0 0 1 0").is_err());
+        assert_eq!(
+            " 1 0 0 0   0 1 0 0   0 0 1 0   0 0 0 1   ",
+            format_projective3(&Projective3::identity())
+        );
+        assert_eq!(matrix, parse_projective3(&format_projective3(&matrix)).unwrap());
+    }
+
+    #[test]
+    fn duration() {
+        assert_eq!(Duration::from_secs(8), parse_duration("0' 08\"").unwrap());
+        assert_eq!(Duration::from_secs(168), parse_duration("2' 48\"").unwrap());
+        assert!(parse_duration("2 48").is_err());
+        assert!(parse_duration("a' 48\"").is_err());
+    }
+
+    #[test]
+    fn timestamp() {
+        assert_eq!(
+            "2016-08-25 15:21:06.815",
+            parse_timestamp("2016-08-25 15:21:06+815").unwrap().to_string()
+        );
+        assert!(parse_timestamp("2016-08-25 15:21:06").is_err());
+        assert!(parse_timestamp("2016-08-25").is_err());
+    }
+
+    #[test]
+    fn vector3() {
+        let vector = parse_vector3(" -16.002730909121258 -7.7914503973701389 11.430690765380859 ")
+            .unwrap();
+        assert_eq!(Vector3::new(-16.002730909121258, -7.7914503973701389, 11.430690765380859), vector);
+        assert!(parse_vector3("1 2").is_err());
+        assert!(parse_vector3("1 2 3 4").is_err());
+        assert!(parse_vector3("1 2 foo").is_err());
     }
 }
@@ -0,0 +1,642 @@
+use {Error, MountCalibration, Result, utils};
+use camera_calibration::OpenCv;
+use nalgebra::Projective3;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Edits the text of a `project.rsp` in place.
+///
+/// The writer works on the original text of the file, not on a parsed tree, so every element that
+/// isn't set through the writer is saved byte-for-byte as it was read. The typed setters, e.g.
+/// `set_sop`, also leave an element alone if its text already parses to the new value. Elements
+/// are addressed by paths of steps below the root `project` element. Each step is a tag name and,
+/// optionally, the element's `name` attribute, e.g. `[("scanpositions", None), ("scanposition",
+/// Some("SP01")), ("text", None)]`. Names are compared as they are, so they can contain any
+/// character.
+///
+/// # Examples
+///
+/// ```
+/// use riscan_pro::ProjectWriter;
+/// let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+/// writer.set_scan_position_frozen("SP01", true).unwrap();
+/// assert!(writer.as_str().contains("<freeze>1</freeze>"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectWriter {
+    path: PathBuf,
+    text: String,
+}
+
+/// A step in a path, i.e. a tag name and an optional name attribute.
+type Step<'a> = (&'a str, Option<&'a str>);
+
+/// The byte positions of an element in the text.
+#[derive(Clone, Copy, Debug)]
+struct Span {
+    start: usize,
+    open_end: usize,
+    close_start: usize,
+    end: usize,
+}
+
+impl ProjectWriter {
+    /// Creates a writer for the project at the path.
+    ///
+    /// The path can be either the `.RiSCAN` directory or the contained `project.rsp`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::ProjectWriter;
+    /// let writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<ProjectWriter> {
+        use project;
+
+        let path = project::rsp_path(path)?;
+        Ok(ProjectWriter {
+            text: fs::read_to_string(&path)?,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Returns the path to the project rsp file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::ProjectWriter;
+    /// let writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// assert!(writer.path().ends_with("project.rsp"));
+    /// ```
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the path to which the previous project rsp file is backed up on save.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::ProjectWriter;
+    /// let writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// assert!(writer.backup_path().ends_with("project.rsp.bak"));
+    /// ```
+    pub fn backup_path(&self) -> PathBuf {
+        self.path.with_extension("rsp.bak")
+    }
+
+    /// Returns the current text of the project rsp file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::ProjectWriter;
+    /// let writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// assert!(writer.as_str().starts_with("<?xml"));
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the text of the element at the path, unescaped.
+    ///
+    /// Empty elements have empty text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::ProjectWriter;
+    /// let writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// let path = [("scanpositions", None), ("scanposition", Some("SP01")), ("name", None)];
+    /// assert_eq!("SP01", writer.text(&path).unwrap());
+    /// let path = [("scanpositions", None), ("scanposition", Some("SP01")), ("text", None)];
+    /// assert_eq!("", writer.text(&path).unwrap());
+    /// ```
+    pub fn text(&self, path: &[(&str, Option<&str>)]) -> Result<String> {
+        let span = find(&self.text, path)?;
+        Ok(unescape(&self.text[span.open_end..span.close_start]))
+    }
+
+    /// Sets the text of the element at the path.
+    ///
+    /// The text is escaped. Empty elements, e.g. `<text/>`, are expanded to hold the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::ProjectWriter;
+    /// let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// let path = [("scanpositions", None), ("scanposition", Some("SP01")), ("text", None)];
+    /// writer.set_text(&path, "Scanned twice").unwrap();
+    /// assert!(writer.as_str().contains("<text>Scanned twice</text>"));
+    /// let path = [("scanpositions", None), ("scanposition", Some("SP03")), ("text", None)];
+    /// assert!(writer.set_text(&path, "").is_err());
+    /// ```
+    pub fn set_text(&mut self, path: &[(&str, Option<&str>)], text: &str) -> Result<()> {
+        let span = find(&self.text, path)?;
+        let text = escape(text);
+        if span.open_end == span.end {
+            let open = self.text[span.start..span.end - 2].trim_end().to_string();
+            let name = tag_name(&self.text, span.start);
+            let element = format!("{}>{}</{}>", open, text, name);
+            self.text.replace_range(span.start..span.end, &element);
+        } else {
+            self.text.replace_range(span.open_end..span.close_start, &text);
+        }
+        Ok(())
+    }
+
+    /// Sets the project's own position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate nalgebra;
+    /// # extern crate riscan_pro;
+    /// # fn main() {
+    /// use riscan_pro::ProjectWriter;
+    /// use nalgebra::Projective3;
+    /// let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// writer.set_pop(&Projective3::identity()).unwrap();
+    /// # }
+    /// ```
+    pub fn set_pop(&mut self, pop: &Projective3<f64>) -> Result<()> {
+        self.set_projective3(&child_path(Vec::new(), "pop/matrix"), pop)
+    }
+
+    /// Sets a scan position's own position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate nalgebra;
+    /// # extern crate riscan_pro;
+    /// # fn main() {
+    /// use riscan_pro::ProjectWriter;
+    /// use nalgebra::Projective3;
+    /// let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// writer.set_sop("SP01", &Projective3::identity()).unwrap();
+    /// # }
+    /// ```
+    pub fn set_sop(&mut self, scan_position_name: &str, sop: &Projective3<f64>) -> Result<()> {
+        let path = child_path(scan_position_path(scan_position_name), "sop/matrix");
+        self.set_projective3(&path, sop)
+    }
+
+    /// Sets whether a scan position's SOP is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::ProjectWriter;
+    /// let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// writer.set_scan_position_frozen("SP01", true).unwrap();
+    /// ```
+    pub fn set_scan_position_frozen(
+        &mut self,
+        scan_position_name: &str,
+        frozen: bool,
+    ) -> Result<()> {
+        let path = child_path(scan_position_path(scan_position_name), "sop/freeze");
+        self.set_flag(&path, frozen)
+    }
+
+    /// Sets a scan position image's camera's own position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate nalgebra;
+    /// # extern crate riscan_pro;
+    /// # fn main() {
+    /// use riscan_pro::ProjectWriter;
+    /// use nalgebra::Projective3;
+    /// let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// writer.set_cop("SP01", "SP01 - Image001", &Projective3::identity()).unwrap();
+    /// # }
+    /// ```
+    pub fn set_cop(
+        &mut self,
+        scan_position_name: &str,
+        image_name: &str,
+        cop: &Projective3<f64>,
+    ) -> Result<()> {
+        let path = child_path(image_path(scan_position_name, image_name), "cop/matrix");
+        self.set_projective3(&path, cop)
+    }
+
+    /// Sets whether a scan position image's COP is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::ProjectWriter;
+    /// let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// writer.set_cop_frozen("SP01", "SP01 - Image001", true).unwrap();
+    /// ```
+    pub fn set_cop_frozen(
+        &mut self,
+        scan_position_name: &str,
+        image_name: &str,
+        frozen: bool,
+    ) -> Result<()> {
+        let path = child_path(image_path(scan_position_name, image_name), "cop/freeze");
+        self.set_flag(&path, frozen)
+    }
+
+    /// Sets a project image's camera's own position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate nalgebra;
+    /// # extern crate riscan_pro;
+    /// # fn main() {
+    /// use riscan_pro::ProjectWriter;
+    /// use nalgebra::Projective3;
+    /// let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// writer.set_project_image_cop("Logo", &Projective3::identity()).unwrap();
+    /// # }
+    /// ```
+    pub fn set_project_image_cop(
+        &mut self,
+        image_name: &str,
+        cop: &Projective3<f64>,
+    ) -> Result<()> {
+        let path = child_path(project_image_path(image_name), "cop/matrix");
+        self.set_projective3(&path, cop)
+    }
+
+    /// Sets whether a project image's COP is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::ProjectWriter;
+    /// let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// writer.set_project_image_cop_frozen("Logo", true).unwrap();
+    /// ```
+    pub fn set_project_image_cop_frozen(&mut self, image_name: &str, frozen: bool) -> Result<()> {
+        let path = child_path(project_image_path(image_name), "cop/freeze");
+        self.set_flag(&path, frozen)
+    }
+
+    /// Sets a mount calibration's matrix and adjusted flag.
+    ///
+    /// The mount calibration is found by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{Project, ProjectWriter};
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let mut mount_calibration = project.mount_calibrations.values().next().unwrap().clone();
+    /// mount_calibration.is_adjusted = true;
+    /// let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// writer.set_mount_calibration(&mount_calibration).unwrap();
+    /// assert!(writer.as_str().contains("<adjusted>1</adjusted>"));
+    /// ```
+    pub fn set_mount_calibration(&mut self, mount_calibration: &MountCalibration) -> Result<()> {
+        let path = vec![
+            ("calibrations", None),
+            ("mountcalibs", None),
+            ("mountcalib", Some(mount_calibration.name.as_str())),
+        ];
+        self.set_projective3(
+            &child_path(path.clone(), "matrix"),
+            &mount_calibration.matrix,
+        )?;
+        self.set_flag(
+            &child_path(path, "adjusted"),
+            mount_calibration.is_adjusted,
+        )
+    }
+
+    /// Sets an OpenCV camera calibration's intrinsic parameters and angle extents.
+    ///
+    /// The camera calibration is found by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{Project, ProjectWriter};
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let camera_calibration = project.camera_calibrations.values().next().unwrap();
+    /// let mut opencv = camera_calibration.as_opencv().unwrap().clone();
+    /// opencv.k1 = 0.;
+    /// let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// writer.set_camera_calibration(&opencv).unwrap();
+    /// assert!(writer.as_str().contains("<k1>0</k1>"));
+    /// ```
+    pub fn set_camera_calibration(&mut self, opencv: &OpenCv) -> Result<()> {
+        let path = vec![
+            ("calibrations", None),
+            ("camcalibs", None),
+            ("camcalib_opencv", Some(opencv.name.as_str())),
+        ];
+        let values = [
+            ("internal_opencv/cx", opencv.cx),
+            ("internal_opencv/cy", opencv.cy),
+            ("internal_opencv/fx", opencv.fx),
+            ("internal_opencv/fy", opencv.fy),
+            ("internal_opencv/k1", opencv.k1),
+            ("internal_opencv/k2", opencv.k2),
+            ("internal_opencv/k3", opencv.k3),
+            ("internal_opencv/k4", opencv.k4),
+            ("internal_opencv/p1", opencv.p1),
+            ("internal_opencv/p2", opencv.p2),
+            ("angle_extents/tan_max_horz", opencv.tan_max_horz),
+            ("angle_extents/tan_max_vert", opencv.tan_max_vert),
+            ("angle_extents/tan_min_horz", opencv.tan_min_horz),
+            ("angle_extents/tan_min_vert", opencv.tan_min_vert),
+        ];
+        for &(child, value) in &values {
+            self.set_f64(&child_path(path.clone(), child), value)?;
+        }
+        Ok(())
+    }
+
+    /// Saves the text to the project rsp file, after copying the previous file to the backup path.
+    ///
+    /// The text is written to a temporary file next to the rsp file, which then replaces the rsp
+    /// file, so a failed write leaves the previous file intact. Returns the backup path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use riscan_pro::ProjectWriter;
+    /// let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+    /// writer.set_scan_position_frozen("SP01", true).unwrap();
+    /// let backup_path = writer.save().unwrap();
+    /// ```
+    pub fn save(&self) -> Result<PathBuf> {
+        let backup_path = self.backup_path();
+        fs::copy(&self.path, &backup_path)?;
+        let temporary_path = self.path.with_extension("rsp.tmp");
+        fs::write(&temporary_path, &self.text)?;
+        fs::rename(&temporary_path, &self.path)?;
+        Ok(backup_path)
+    }
+
+    fn set_projective3(&mut self, path: &[Step], matrix: &Projective3<f64>) -> Result<()> {
+        if utils::parse_projective3(&self.text(path)?).ok().as_ref() == Some(matrix) {
+            Ok(())
+        } else {
+            self.set_text(path, &utils::format_projective3(matrix))
+        }
+    }
+
+    fn set_f64(&mut self, path: &[Step], value: f64) -> Result<()> {
+        if self.text(path)?.trim().parse::<f64>().ok() == Some(value) {
+            Ok(())
+        } else {
+            self.set_text(path, &value.to_string())
+        }
+    }
+
+    fn set_flag(&mut self, path: &[Step], value: bool) -> Result<()> {
+        let flag = if value { "1" } else { "0" };
+        if self.text(path)?.trim() == flag {
+            Ok(())
+        } else {
+            self.set_text(path, flag)
+        }
+    }
+}
+
+fn scan_position_path<'a>(scan_position_name: &'a str) -> Vec<Step<'a>> {
+    vec![("scanpositions", None), ("scanposition", Some(scan_position_name))]
+}
+
+fn image_path<'a>(scan_position_name: &'a str, image_name: &'a str) -> Vec<Step<'a>> {
+    let mut path = scan_position_path(scan_position_name);
+    path.push(("scanposimages", None));
+    path.push(("scanposimage", Some(image_name)));
+    path
+}
+
+fn project_image_path<'a>(image_name: &'a str) -> Vec<Step<'a>> {
+    vec![("geometry_objects", None), ("images", None), ("image", Some(image_name))]
+}
+
+/// Appends the slash-separated tag names, without name attributes, to the path.
+fn child_path<'a>(mut path: Vec<Step<'a>>, tag_names: &'a str) -> Vec<Step<'a>> {
+    path.extend(tag_names.split('/').map(|tag_name| (tag_name, None)));
+    path
+}
+
+/// Formats a path for error messages, e.g. `scanpositions/scanposition[SP01]/sop`.
+fn display_path(path: &[Step]) -> String {
+    path.iter()
+        .map(|&(tag_name, name)| match name {
+            Some(name) => format!("{}[{}]", tag_name, name),
+            None => tag_name.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Finds the first element at the path below the root element.
+fn find(text: &str, steps: &[Step]) -> Result<Span> {
+    let mut stack: Vec<bool> = Vec::new();
+    let mut open: Option<(usize, usize)> = None;
+    let mut position = 0;
+    while let Some(offset) = text[position..].find('<') {
+        let tag_start = position + offset;
+        let rest = &text[tag_start..];
+        if rest.starts_with("<?") {
+            position = skip(text, tag_start, "?>")?;
+        } else if rest.starts_with("<!--") {
+            position = skip(text, tag_start, "-->")?;
+        } else if rest.starts_with("<![CDATA[") {
+            position = skip(text, tag_start, "]]>")?;
+        } else if rest.starts_with("<!") {
+            position = skip(text, tag_start, ">")?;
+        } else if rest.starts_with("</") {
+            position = skip(text, tag_start, ">")?;
+            stack.pop();
+            if let Some((open_start, open_stop)) = open {
+                if stack.len() == steps.len() {
+                    return Ok(Span {
+                        start: open_start,
+                        open_end: open_stop,
+                        close_start: tag_start,
+                        end: position,
+                    });
+                }
+            }
+        } else {
+            let tag_stop = tag_end(text, tag_start)?;
+            let is_empty = text[..tag_stop].ends_with("/>");
+            let depth = stack.len();
+            let is_match = open.is_none() && depth > 0 && depth <= steps.len() &&
+                stack[1..].iter().all(|&matches| matches) &&
+                {
+                    let (name, attribute) = steps[depth - 1];
+                    tag_name(text, tag_start) == name &&
+                        match attribute {
+                            Some(attribute) => {
+                                name_attribute(&text[tag_start..tag_stop]).as_deref() ==
+                                    Some(attribute)
+                            }
+                            None => true,
+                        }
+                };
+            if is_match && depth == steps.len() {
+                if is_empty {
+                    return Ok(Span {
+                        start: tag_start,
+                        open_end: tag_stop,
+                        close_start: tag_stop,
+                        end: tag_stop,
+                    });
+                }
+                open = Some((tag_start, tag_stop));
+            }
+            if !is_empty {
+                stack.push(depth == 0 || is_match);
+            }
+            position = tag_stop;
+        }
+    }
+    Err(Error::MissingElement(display_path(steps)))
+}
+
+fn skip(text: &str, start: usize, terminator: &str) -> Result<usize> {
+    text[start..]
+        .find(terminator)
+        .map(|i| start + i + terminator.len())
+        .ok_or(Error::MalformedXml(start))
+}
+
+/// Returns the position just past the end of the tag that starts at `start`, skipping over quoted
+/// attribute values.
+fn tag_end(text: &str, start: usize) -> Result<usize> {
+    let mut quote = None;
+    for (i, c) in text[start..].char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Ok(start + i + 1),
+            _ => {}
+        }
+    }
+    Err(Error::MalformedXml(start))
+}
+
+fn tag_name(text: &str, start: usize) -> &str {
+    let rest = &text[start + 1..];
+    let end = rest.find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .unwrap_or(rest.len());
+    &rest[..end]
+}
+
+fn name_attribute(tag: &str) -> Option<String> {
+    let mut rest = tag;
+    while let Some(i) = rest.find("name=") {
+        let is_attribute = rest[..i].ends_with(char::is_whitespace);
+        rest = &rest[i + 5..];
+        if let Some(quote) = rest.chars().next().filter(|&c| c == '"' || c == '\'') {
+            if let Some(end) = rest[1..].find(quote) {
+                if is_attribute {
+                    return Some(unescape(&rest[1..end + 1]));
+                }
+                rest = &rest[end + 1..];
+            }
+        }
+    }
+    None
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_path() {
+        let path = child_path(image_path("SP01", "SP01/a [b]"), "cop/matrix");
+        assert_eq!(
+            "scanpositions/scanposition[SP01]/scanposimages/scanposimage[SP01/a [b]]/cop/matrix",
+            super::display_path(&path)
+        );
+    }
+
+    #[test]
+    fn find() {
+        let text = r#"<?xml version="1.0"?>
+<!-- <project> -->
+<project name="p">
+  <a name="x"><b>1</b></a>
+  <a name="y" kind="a>b"><b/><c>2</c></a>
+  <a name="z]/[&amp;"><b>4</b></a>
+  <b>3</b>
+</project>
+"#;
+        let span = super::find(text, &[("a", Some("y")), ("c", None)]).unwrap();
+        assert_eq!("2", &text[span.open_end..span.close_start]);
+        let span = super::find(text, &[("a", Some("x")), ("b", None)]).unwrap();
+        assert_eq!("1", &text[span.open_end..span.close_start]);
+        let span = super::find(text, &[("b", None)]).unwrap();
+        assert_eq!("3", &text[span.open_end..span.close_start]);
+        let span = super::find(text, &[("a", Some("y")), ("b", None)]).unwrap();
+        assert_eq!("<b/>", &text[span.start..span.end]);
+        let span = super::find(text, &[("a", Some("z]/[&")), ("b", None)]).unwrap();
+        assert_eq!("4", &text[span.open_end..span.close_start]);
+        assert!(super::find(text, &[("a", Some("w")), ("b", None)]).is_err());
+        assert!(super::find(text, &[("c", None)]).is_err());
+    }
+
+    #[test]
+    fn names_with_brackets() {
+        let mut writer = ProjectWriter {
+            path: PathBuf::from("project.rsp"),
+            text: r#"<project>
+  <scanpositions>
+    <scanposition name="SP[1"><sop><freeze>0</freeze></sop></scanposition>
+    <scanposition name="SP]2"><sop><freeze>0</freeze></sop></scanposition>
+  </scanpositions>
+</project>"#
+                .to_string(),
+        };
+        writer.set_scan_position_frozen("SP]2", true).unwrap();
+        let path = child_path(scan_position_path("SP[1"), "sop/freeze");
+        assert_eq!("0", writer.text(&path).unwrap());
+        let path = child_path(scan_position_path("SP]2"), "sop/freeze");
+        assert_eq!("1", writer.text(&path).unwrap());
+    }
+
+    #[test]
+    fn set_text_unchanged() {
+        let writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+        let mut other = writer.clone();
+        let path = child_path(scan_position_path("SP02"), "sop/freeze");
+        let span = super::find(&writer.text, &path).unwrap();
+        let freeze = writer.text[span.open_end..span.close_start].to_string();
+        other.set_text(&path, &freeze).unwrap();
+        assert_eq!(writer, other);
+    }
+
+    #[test]
+    fn set_text_empty_element() {
+        let mut writer = ProjectWriter::from_path("data/project.RiSCAN").unwrap();
+        let path = child_path(scan_position_path("SP01"), "text");
+        writer.set_text(&path, "a < b").unwrap();
+        assert!(writer.as_str().contains("<text>a &lt; b</text>"));
+    }
+}